@@ -0,0 +1,21 @@
+#include "Greeter.h"
+#include "main.h"
+#include <stdio.h>
+Greeter* Greeter_create(int count) { 
+	Greeter* self = malloc(sizeof(Greeter));
+	((self->count) = 0);
+	((self->count) = count);
+	return self;
+}
+void Greeter_free(Greeter* self) { 
+	if (self == NULL) return;
+	if (((self->count) == 1)) {
+		printf(" once\n");
+	}
+	fflush(stdout);
+	free(self);
+}
+Greeter* Greeter_replace(Greeter* old, Greeter* value) { 
+	if (old != value) Greeter_free(old);
+	return value;
+}
@@ -0,0 +1,8 @@
+#pragma once
+#include <stdlib.h>
+typedef struct GreeterT {
+	int count;
+} Greeter;
+Greeter* Greeter_create(int count);
+void Greeter_free(Greeter* self);
+Greeter* Greeter_replace(Greeter* old, Greeter* value);
@@ -1,5 +1,33 @@
 #include "I16.h"
-I16* create(short value) { 
+#include "numbers.h"
+I16* I16_create(short value) { 
 	I16* self = malloc(sizeof(I16));
 	((self->inner) = value);
+	return self;
+}
+I16* I16_add(I16* self, I16* other) { 
+	return I16_create(((self->inner) + (other->inner)));
+}
+I16* I16_sub(I16* self, I16* other) { 
+	return I16_create(((self->inner) - (other->inner)));
+}
+I16* I16_mul(I16* self, I16* other) { 
+	return I16_create(((self->inner) * (other->inner)));
+}
+I16* I16_div(I16* self, I16* other) { 
+	return I16_create(((self->inner) / (other->inner)));
+}
+bool I16_eq(I16* self, I16* other) { 
+	return ((self->inner) == (other->inner));
+}
+bool I16_lt(I16* self, I16* other) { 
+	return ((self->inner) < (other->inner));
+}
+void I16_free(I16* self) { 
+	if (self == NULL) return;
+	free(self);
+}
+I16* I16_replace(I16* old, I16* value) { 
+	if (old != value) I16_free(old);
+	return value;
 }
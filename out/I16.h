@@ -1,4 +1,15 @@
+#pragma once
+#include <stdlib.h>
+#include <stdbool.h>
 typedef struct I16T {
 	short inner;
 } I16;
-I16* create(short value);
+I16* I16_create(short value);
+I16* I16_add(I16* self, I16* other);
+I16* I16_sub(I16* self, I16* other);
+I16* I16_mul(I16* self, I16* other);
+I16* I16_div(I16* self, I16* other);
+bool I16_eq(I16* self, I16* other);
+bool I16_lt(I16* self, I16* other);
+void I16_free(I16* self);
+I16* I16_replace(I16* old, I16* value);
@@ -1,5 +1,33 @@
 #include "I32.h"
-I32* create(int value) { 
+#include "numbers.h"
+I32* I32_create(int value) { 
 	I32* self = malloc(sizeof(I32));
 	((self->inner) = value);
+	return self;
+}
+I32* I32_add(I32* self, I32* other) { 
+	return I32_create(((self->inner) + (other->inner)));
+}
+I32* I32_sub(I32* self, I32* other) { 
+	return I32_create(((self->inner) - (other->inner)));
+}
+I32* I32_mul(I32* self, I32* other) { 
+	return I32_create(((self->inner) * (other->inner)));
+}
+I32* I32_div(I32* self, I32* other) { 
+	return I32_create(((self->inner) / (other->inner)));
+}
+bool I32_eq(I32* self, I32* other) { 
+	return ((self->inner) == (other->inner));
+}
+bool I32_lt(I32* self, I32* other) { 
+	return ((self->inner) < (other->inner));
+}
+void I32_free(I32* self) { 
+	if (self == NULL) return;
+	free(self);
+}
+I32* I32_replace(I32* old, I32* value) { 
+	if (old != value) I32_free(old);
+	return value;
 }
@@ -1,4 +1,15 @@
+#pragma once
+#include <stdlib.h>
+#include <stdbool.h>
 typedef struct I32T {
 	int inner;
 } I32;
-I32* create(int value);
+I32* I32_create(int value);
+I32* I32_add(I32* self, I32* other);
+I32* I32_sub(I32* self, I32* other);
+I32* I32_mul(I32* self, I32* other);
+I32* I32_div(I32* self, I32* other);
+bool I32_eq(I32* self, I32* other);
+bool I32_lt(I32* self, I32* other);
+void I32_free(I32* self);
+I32* I32_replace(I32* old, I32* value);
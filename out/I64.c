@@ -1,5 +1,33 @@
 #include "I64.h"
-I64* create(long value) { 
+#include "numbers.h"
+I64* I64_create(long value) { 
 	I64* self = malloc(sizeof(I64));
 	((self->inner) = value);
+	return self;
+}
+I64* I64_add(I64* self, I64* other) { 
+	return I64_create(((self->inner) + (other->inner)));
+}
+I64* I64_sub(I64* self, I64* other) { 
+	return I64_create(((self->inner) - (other->inner)));
+}
+I64* I64_mul(I64* self, I64* other) { 
+	return I64_create(((self->inner) * (other->inner)));
+}
+I64* I64_div(I64* self, I64* other) { 
+	return I64_create(((self->inner) / (other->inner)));
+}
+bool I64_eq(I64* self, I64* other) { 
+	return ((self->inner) == (other->inner));
+}
+bool I64_lt(I64* self, I64* other) { 
+	return ((self->inner) < (other->inner));
+}
+void I64_free(I64* self) { 
+	if (self == NULL) return;
+	free(self);
+}
+I64* I64_replace(I64* old, I64* value) { 
+	if (old != value) I64_free(old);
+	return value;
 }
@@ -1,4 +1,15 @@
+#pragma once
+#include <stdlib.h>
+#include <stdbool.h>
 typedef struct I64T {
 	long inner;
 } I64;
-I64* create(long value);
+I64* I64_create(long value);
+I64* I64_add(I64* self, I64* other);
+I64* I64_sub(I64* self, I64* other);
+I64* I64_mul(I64* self, I64* other);
+I64* I64_div(I64* self, I64* other);
+bool I64_eq(I64* self, I64* other);
+bool I64_lt(I64* self, I64* other);
+void I64_free(I64* self);
+I64* I64_replace(I64* old, I64* value);
@@ -1,5 +1,33 @@
 #include "I8.h"
-I8* create(char value) { 
+#include "numbers.h"
+I8* I8_create(char value) { 
 	I8* self = malloc(sizeof(I8));
 	((self->inner) = value);
+	return self;
+}
+I8* I8_add(I8* self, I8* other) { 
+	return I8_create(((self->inner) + (other->inner)));
+}
+I8* I8_sub(I8* self, I8* other) { 
+	return I8_create(((self->inner) - (other->inner)));
+}
+I8* I8_mul(I8* self, I8* other) { 
+	return I8_create(((self->inner) * (other->inner)));
+}
+I8* I8_div(I8* self, I8* other) { 
+	return I8_create(((self->inner) / (other->inner)));
+}
+bool I8_eq(I8* self, I8* other) { 
+	return ((self->inner) == (other->inner));
+}
+bool I8_lt(I8* self, I8* other) { 
+	return ((self->inner) < (other->inner));
+}
+void I8_free(I8* self) { 
+	if (self == NULL) return;
+	free(self);
+}
+I8* I8_replace(I8* old, I8* value) { 
+	if (old != value) I8_free(old);
+	return value;
 }
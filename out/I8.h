@@ -1,4 +1,15 @@
+#pragma once
+#include <stdlib.h>
+#include <stdbool.h>
 typedef struct I8T {
 	char inner;
 } I8;
-I8* create(char value);
+I8* I8_create(char value);
+I8* I8_add(I8* self, I8* other);
+I8* I8_sub(I8* self, I8* other);
+I8* I8_mul(I8* self, I8* other);
+I8* I8_div(I8* self, I8* other);
+bool I8_eq(I8* self, I8* other);
+bool I8_lt(I8* self, I8* other);
+void I8_free(I8* self);
+I8* I8_replace(I8* old, I8* value);
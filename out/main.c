@@ -1,8 +1,11 @@
 #include "main.h"
 #include <stdio.h>
-void say_hello() { 
+static void say_hello();
+static void say_hello() { 
 	printf("Hello");
 }
 void main() { 
+	Greeter* greeter = Greeter_create(1);
 	say_hello();
+	Greeter_free(greeter);
 }
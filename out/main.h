@@ -1 +1,2 @@
-void say_hello();
+#pragma once
+#include "Greeter.h"
@@ -0,0 +1,5 @@
+#pragma once
+#include "I8.h"
+#include "I16.h"
+#include "I32.h"
+#include "I64.h"
@@ -1,28 +1,215 @@
 use core::panic;
 use std::{fmt::Display, sync::{Arc, Mutex}};
 
-use crate::{util::{file::SourceFile, position::Positioned}, parser::node::{Node, ValueNode, Operator, VarType, FunctionDefinitionParameter, FunctionCallParameter}};
+use crate::{util::{file::SourceFile, position::Positioned}, parser::node::{Node, ValueNode, InterpolationPart, Operator, UnaryOperator, VarType, FunctionDefinitionParameter, FunctionCallParameter, TypeExpr, Attribute}};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum DataType {
     Void,
     CDecimal,
+    CFloat,
     CString,
+    Array(Box<DataType>, usize),
+    Slice(Box<DataType>),
+    Pointer(bool, Box<DataType>),
+    Optional(Box<DataType>),
+    Tuple(Vec<DataType>),
+    Number(NumberType),
+    Function(Vec<DataType>, Box<DataType>),
+    Closure(Vec<DataType>, Box<DataType>),
+    Generic(String, Vec<DataType>),
+    ResultValue(bool, Box<DataType>), // ok(value) or err(error) before getting the type of its Result
+    Null,
+    None,
     Custom(String)
 }
 
+impl DataType {
+
+    // Literal types as they are stored in C
+    pub fn normalized(&self) -> DataType {
+        match self {
+            DataType::CDecimal => DataType::Custom("c_int".to_string()),
+            DataType::CFloat => DataType::Custom("c_double".to_string()),
+            DataType::CString => DataType::Custom("c_string".to_string()),
+            DataType::Tuple(elements) => DataType::Tuple(elements.iter().map(|element| element.normalized()).collect()),
+            _ => self.clone()
+        }
+    }
+
+    pub fn is_named(&self, name: &str) -> bool {
+        matches!(self.normalized(), DataType::Custom(custom) if custom == name)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, DataType::Number(_)) || ["c_char", "c_short", "c_int", "c_long", "c_float", "c_double"].iter().any(|name| self.is_named(name))
+    }
+
+}
+
 impl Display for DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DataType::Void => write!(f, "void")?,
             DataType::CDecimal => write!(f, "int")?,
-            DataType::CString => todo!("C String"),
+            DataType::CFloat => write!(f, "double")?,
+            DataType::CString => write!(f, "c_string")?,
+            DataType::Array(element, size) => write!(f, "[{}; {}]", element, size)?,
+            DataType::Slice(element) => write!(f, "[{}]", element)?,
+            DataType::Pointer(constant, inner) => {
+                write!(f, "*")?;
+                if *constant {
+                    write!(f, "const ")?;
+                }
+                write!(f, "{}", inner)?;
+            },
+            DataType::Optional(inner) => write!(f, "{}?", inner)?,
+            DataType::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, ")")?;
+            },
+            DataType::Number(number) => write!(f, "{}", number)?,
+            DataType::Null => write!(f, "null")?,
+            DataType::None => write!(f, "none")?,
+            DataType::Function(params, return_type) |
+            DataType::Closure(params, return_type) => {
+                if let DataType::Closure(_, _) = self {
+                    write!(f, "closure ")?;
+                }
+                write!(f, "fn(")?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param)?;
+                }
+                write!(f, "): {}", return_type)?;
+            },
+            DataType::Generic(name, params) => {
+                write!(f, "{}[", name)?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param)?;
+                }
+                write!(f, "]")?;
+            },
+            DataType::ResultValue(true, inner) => write!(f, "ok({})", inner)?,
+            DataType::ResultValue(false, inner) => write!(f, "err({})", inner)?,
             DataType::Custom(custom) => write!(f, "{}", custom)?,
         }
         Ok(())
     }
 }
 
+// Fixed-width numbers (usize and isize are pointer sized, 64 bits are assumed)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberType {
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    Isize,
+    F32,
+    F64
+}
+
+impl NumberType {
+
+    pub fn from_name(name: &str) -> Option<NumberType> {
+        match name {
+            "u8" => Some(NumberType::U8),
+            "u16" => Some(NumberType::U16),
+            "u32" => Some(NumberType::U32),
+            "u64" => Some(NumberType::U64),
+            "usize" => Some(NumberType::Usize),
+            "i8" => Some(NumberType::I8),
+            "i16" => Some(NumberType::I16),
+            "i32" => Some(NumberType::I32),
+            "i64" => Some(NumberType::I64),
+            "isize" => Some(NumberType::Isize),
+            "f32" => Some(NumberType::F32),
+            "f64" => Some(NumberType::F64),
+            _ => None
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            NumberType::U8 | NumberType::I8 => 8,
+            NumberType::U16 | NumberType::I16 => 16,
+            NumberType::U32 | NumberType::I32 | NumberType::F32 => 32,
+            NumberType::U64 | NumberType::I64 | NumberType::F64 | NumberType::Usize | NumberType::Isize => 64
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, NumberType::F32 | NumberType::F64)
+    }
+
+    pub fn is_signed(&self) -> bool {
+        !matches!(self, NumberType::U8 | NumberType::U16 | NumberType::U32 | NumberType::U64 | NumberType::Usize)
+    }
+
+    pub fn range(&self) -> Option<(i128, i128)> {
+        match (self.is_float(), self.is_signed()) {
+            (true, _) => None,
+            (false, true) => Some((-(1 << (self.bits() - 1)), (1 << (self.bits() - 1)) - 1)),
+            (false, false) => Some((0, (1 << self.bits()) - 1))
+        }
+    }
+
+    // Implicit conversions never change the sign of a value
+    pub fn converts_to(&self, expected: &NumberType) -> bool {
+        match (self.is_float(), expected.is_float()) {
+            (true, false) => false,
+            (_, true) => true,
+            (false, false) if self.is_signed() == expected.is_signed() => true,
+            (false, false) => !self.is_signed() && expected.bits() > self.bits()
+        }
+    }
+
+    // Values of the found type which the expected type cannot represent exactly
+    pub fn is_lossy(&self, expected: &NumberType) -> bool {
+        match (self.is_float(), expected.is_float()) {
+            (false, true) => self.bits() >= expected.bits(),
+            _ => self.bits() > expected.bits()
+        }
+    }
+
+}
+
+impl Display for NumberType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberType::U8 => write!(f, "u8"),
+            NumberType::U16 => write!(f, "u16"),
+            NumberType::U32 => write!(f, "u32"),
+            NumberType::U64 => write!(f, "u64"),
+            NumberType::Usize => write!(f, "usize"),
+            NumberType::I8 => write!(f, "i8"),
+            NumberType::I16 => write!(f, "i16"),
+            NumberType::I32 => write!(f, "i32"),
+            NumberType::I64 => write!(f, "i64"),
+            NumberType::Isize => write!(f, "isize"),
+            NumberType::F32 => write!(f, "f32"),
+            NumberType::F64 => write!(f, "f64"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NodeInfo {
     data_type: Option<DataType>, // TODO: change to option and check every time to see if the datatype can be inferred
@@ -46,6 +233,10 @@ pub struct VariableSymbol {
     name: String,
     data_type: Option<DataType>,
     initialized: bool,
+    public: bool,
+    owned: bool, // Frees its class instance when going out of scope
+    moved: bool, // Gave its instance away, cannot be used until reassigned
+    value: Option<i128> // Constants known at compile time
 }
 
 impl VariableSymbol {
@@ -55,7 +246,11 @@ impl VariableSymbol {
             var_type,
             name,
             data_type,
-            initialized
+            initialized,
+            public: true,
+            owned: false,
+            moved: false,
+            value: None
         }
     }
 
@@ -65,7 +260,9 @@ impl VariableSymbol {
 pub enum FunctionType {
     ExternalFunction,
     Constructor,
-    Function
+    Destructor,
+    Function,
+    Static
 }
 
 #[derive(Clone, Debug)]
@@ -73,39 +270,82 @@ pub struct FunctionSymbol {
     name: String,
     data_type: DataType,
     function_type: FunctionType,
-    params: Vec<FunctionDefinitionParameter>
+    params: Vec<DataType>, // Resolved where the function is defined
+    variadic: bool,
+    public: bool,
+    owner: Option<String>, // Class of the method
+    fresh: bool, // Returns a new instance owned by the caller
+    body: Option<(Vec<String>, Vec<Positioned<Node>>)> // Parameter names and checked body of const functions
 }
 
 impl FunctionSymbol {
 
-    pub fn new(name: String, data_type: DataType, function_type: FunctionType, params: Vec<FunctionDefinitionParameter>) -> FunctionSymbol {
+    pub fn new(name: String, data_type: DataType, function_type: FunctionType, params: Vec<DataType>, public: bool, owner: Option<String>) -> FunctionSymbol {
         Self {
             name,
             data_type,
             function_type,
-            params
+            params,
+            variadic: false,
+            public,
+            owner,
+            fresh: false,
+            body: None
+        }
+    }
+
+    // Methods are prefixed by their class in C
+    pub fn c_name(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("{}_{}", owner, self.name),
+            None => self.name.clone()
         }
     }
 
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClassKind {
+    Class,
+    Opaque, // extern class (incomplete C type used behind pointers)
+    Struct // extern struct (C struct used by value)
+}
+
 #[derive(Clone, Debug)]
 pub struct ClassSymbol {
     name: String,
+    kind: ClassKind,
     fields: Vec<Arc<Mutex<VariableSymbol>>>,
-    functions: Vec<Arc<Mutex<FunctionSymbol>>>
+    functions: Vec<Arc<Mutex<FunctionSymbol>>>,
+    statics: Vec<Arc<Mutex<VariableSymbol>>>, // Globals prefixed by the class in C
+    typedef: bool, // Extern struct named by its typedef in C
+    public: bool
 }
 
 impl ClassSymbol {
 
-    pub fn new(name: String) -> ClassSymbol {
+    pub fn new(name: String, public: bool) -> ClassSymbol {
         Self {
             name,
+            kind: ClassKind::Class,
             fields: Vec::new(),
-            functions: Vec::new()
+            functions: Vec::new(),
+            statics: Vec::new(),
+            typedef: false,
+            public
         }
     }
 
+    pub fn is_private(&self, name: &str) -> bool {
+        self.fields.iter().chain(self.statics.iter()).any(|field| {
+            let field = field.lock().unwrap();
+            field.name == name && !field.public
+        }) || self.functions.iter().any(|function| {
+            let function = function.lock().unwrap();
+            function.name == name && !function.public
+        })
+    }
+
     pub fn get_field(&mut self, name: String) -> Option<Arc<Mutex<VariableSymbol>>> {
         for field in self.fields.iter() {
             if field.lock().unwrap().name == name {
@@ -128,18 +368,84 @@ impl ClassSymbol {
 
 }
 
+#[derive(Clone, Debug)]
+pub struct AliasSymbol {
+    name: String,
+    data_type: DataType,
+    public: bool
+}
+
+#[derive(Clone, Debug)]
+pub struct ModuleSymbol {
+    name: String,
+    functions: Vec<Arc<Mutex<FunctionSymbol>>>,
+    classes: Vec<Arc<Mutex<ClassSymbol>>>,
+    aliases: Vec<AliasSymbol>,
+    variables: Vec<Arc<Mutex<VariableSymbol>>>,
+    imports: Vec<String> // Modules included by its header
+}
+
+impl ModuleSymbol {
+
+    pub fn new(name: String) -> ModuleSymbol {
+        Self {
+            name,
+            functions: Vec::new(),
+            classes: Vec::new(),
+            aliases: Vec::new(),
+            variables: Vec::new(),
+            imports: Vec::new()
+        }
+    }
+
+    pub fn class_names(&self) -> Vec<String> {
+        self.classes.iter().map(|class| class.lock().unwrap().name.clone()).collect()
+    }
+
+    // Classes whose values are pointers in C
+    pub fn instance_names(&self) -> Vec<String> {
+        self.classes.iter().filter(|class| class.lock().unwrap().kind == ClassKind::Class).map(|class| class.lock().unwrap().name.clone()).collect()
+    }
+
+    // Extern structs spelled 'struct name' in C
+    pub fn struct_names(&self) -> Vec<String> {
+        self.classes.iter().filter(|class| {
+            let class = class.lock().unwrap();
+            class.kind == ClassKind::Struct && !class.typedef
+        }).map(|class| class.lock().unwrap().name.clone()).collect()
+    }
+
+    pub fn is_private(&self, name: &str) -> bool {
+        self.classes.iter().any(|class| {
+            let class = class.lock().unwrap();
+            class.name == name && !class.public
+        }) || self.functions.iter().any(|function| {
+            let function = function.lock().unwrap();
+            function.name == name && !function.public
+        }) || self.aliases.iter().any(|alias| alias.name == name && !alias.public) || self.variables.iter().any(|variable| {
+            let variable = variable.lock().unwrap();
+            variable.name == name && !variable.public
+        })
+    }
+
+}
+
 #[derive(Clone, Debug)]
 pub enum Symbol {
     Function(Arc<Mutex<FunctionSymbol>>),
     Variable(Arc<Mutex<VariableSymbol>>),
-    Class(Arc<Mutex<ClassSymbol>>)
+    Class(Arc<Mutex<ClassSymbol>>),
+    Module(Arc<Mutex<ModuleSymbol>>)
 }
 
 #[derive(Clone, Debug)]
 pub enum ScopeType {
     Root,
     Function(String, DataType),
-    Class(String)
+    Lambda,
+    Block,
+    Class(String),
+    Module(String)
 }
 
 #[derive(Clone, Debug)]
@@ -149,6 +455,13 @@ pub struct Scope {
     variables: Vec<Arc<Mutex<VariableSymbol>>>,
     functions: Vec<Arc<Mutex<FunctionSymbol>>>,
     classes: Vec<Arc<Mutex<ClassSymbol>>>,
+    modules: Vec<Arc<Mutex<ModuleSymbol>>>,
+    aliases: Vec<AliasSymbol>,
+    captures: Vec<Arc<Mutex<VariableSymbol>>>,
+    deferred: Vec<Positioned<Node>>,
+    narrowed: Vec<Arc<Mutex<VariableSymbol>>>, // Optionals known to hold a value
+    unsafe_code: bool,
+    static_code: bool, // No self
     selected: Option<Box<Scope>>
 }
 
@@ -161,6 +474,13 @@ impl Scope {
             variables: Vec::new(),
             functions: Vec::new(),
             classes: Vec::new(),
+            modules: Vec::new(),
+            aliases: Vec::new(),
+            captures: Vec::new(),
+            deferred: Vec::new(),
+            narrowed: Vec::new(),
+            unsafe_code: false,
+            static_code: false,
             selected: None,
         }
     }
@@ -168,7 +488,16 @@ impl Scope {
     pub fn symbol_exists(&mut self, name: String) -> bool {
         self.get_variable(name.clone()).is_some() || 
         self.get_function(name.clone()).is_some() || 
-        self.get_class(name.clone()).is_some()
+        self.get_class(name.clone()).is_some() ||
+        self.get_module(name.clone()).is_some() ||
+        self.get_alias(&name).is_some()
+    }
+
+    pub fn get_alias(&self, name: &str) -> Option<AliasSymbol> {
+        if let Some(alias) = self.aliases.iter().find(|alias| alias.name == name) {
+            return Some(alias.clone());
+        }
+        self.parent.as_ref().and_then(|parent| parent.get_alias(name))
     }
 
     pub fn get_variable(&mut self, name: String) -> Option<Arc<Mutex<VariableSymbol>>> {
@@ -209,6 +538,23 @@ impl Scope {
         }
     }
 
+    // Called functions in the checked AST (methods and static functions are prefixed by their class)
+    pub fn get_c_function(&self, c_name: &str) -> Option<Arc<Mutex<FunctionSymbol>>> {
+        for function in self.functions.iter() {
+            if function.lock().unwrap().c_name() == c_name {
+                return Some(function.clone());
+            }
+        }
+        for class in self.classes.iter() {
+            for function in class.lock().unwrap().functions.iter() {
+                if function.lock().unwrap().c_name() == c_name {
+                    return Some(function.clone());
+                }
+            }
+        }
+        self.parent.as_ref().and_then(|parent| parent.get_c_function(c_name))
+    }
+
     pub fn get_class(&mut self, name: String) -> Option<Arc<Mutex<ClassSymbol>>> {
         if let Some(selected) = &mut self.selected {
             let class = selected.get_class(name);
@@ -228,6 +574,109 @@ impl Scope {
         }
     }
 
+    pub fn get_module(&mut self, name: String) -> Option<Arc<Mutex<ModuleSymbol>>> {
+        if let Some(selected) = &mut self.selected {
+            let module = selected.get_module(name);
+            self.selected = None;
+            return module;
+        } 
+
+        for module in self.modules.iter() {
+            if module.lock().unwrap().name == name {
+                return Some(module.clone());
+            }
+        }
+        if let Some(parent) = &mut self.parent {
+            parent.get_module(name)
+        } else {
+            None
+        }
+    }
+
+    // Records the variable in every lambda between its definition and the current scope, returns false for globals
+    pub fn capture(&mut self, variable: &Arc<Mutex<VariableSymbol>>) -> bool {
+        if self.variables.iter().any(|x| Arc::ptr_eq(x, variable)) {
+            return !matches!(self.scope, ScopeType::Root);
+        }
+
+        let Some(parent) = &mut self.parent else {
+            return false;
+        };
+        let found = parent.capture(variable);
+        if found && matches!(self.scope, ScopeType::Lambda) && !self.captures.iter().any(|x| Arc::ptr_eq(x, variable)) {
+            self.captures.push(variable.clone());
+        }
+        found
+    }
+
+    // Variables of the current function
+    pub fn is_local(&self, variable: &Arc<Mutex<VariableSymbol>>) -> bool {
+        if self.variables.iter().any(|x| Arc::ptr_eq(x, variable)) {
+            return matches!(self.scope, ScopeType::Function(_, _) | ScopeType::Block);
+        }
+        match (&self.scope, &self.parent) {
+            (ScopeType::Function(_, _), _) => false,
+            (_, Some(parent)) => parent.is_local(variable),
+            _ => false
+        }
+    }
+
+    pub fn is_narrowed(&self, variable: &Arc<Mutex<VariableSymbol>>) -> bool {
+        if self.narrowed.iter().any(|x| Arc::ptr_eq(x, variable)) {
+            return true;
+        }
+        self.parent.as_ref().is_some_and(|parent| parent.is_narrowed(variable))
+    }
+
+    // Assigned an optional, the variable can be none again
+    pub fn unnarrow(&mut self, variable: &Arc<Mutex<VariableSymbol>>) {
+        self.narrowed.retain(|x| !Arc::ptr_eq(x, variable));
+        if let Some(parent) = &mut self.parent {
+            parent.unnarrow(variable);
+        }
+    }
+
+    pub fn is_unsafe(&self) -> bool {
+        self.unsafe_code || self.parent.as_ref().is_some_and(|parent| parent.is_unsafe())
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.static_code || self.parent.as_ref().is_some_and(|parent| parent.is_static())
+    }
+
+    // Return type of the function containing the block
+    pub fn return_type(&self) -> Option<DataType> {
+        match (&self.scope, &self.parent) {
+            (ScopeType::Function(_, data_type), _) => Some(data_type.clone()),
+            (ScopeType::Block, Some(parent)) => parent.return_type(),
+            _ => None
+        }
+    }
+
+    pub fn class_name(&self) -> Option<String> {
+        match (&self.scope, &self.parent) {
+            (ScopeType::Class(class), _) => Some(class.clone()),
+            (_, Some(parent)) => parent.class_name(),
+            _ => None
+        }
+    }
+
+    pub fn in_class(&self, name: &str) -> bool {
+        match (&self.scope, &self.parent) {
+            (ScopeType::Class(class), _) if class == name => true,
+            (_, Some(parent)) => parent.in_class(name),
+            _ => false
+        }
+    }
+
+    pub fn is_captured(&self, variable: &Arc<Mutex<VariableSymbol>>) -> bool {
+        match (&self.scope, &self.parent) {
+            (ScopeType::Lambda, _) => self.captures.iter().any(|x| Arc::ptr_eq(x, variable)),
+            (_, Some(parent)) => parent.is_captured(variable),
+            _ => false
+        }
+    }
+
 }
 
 pub struct Checker {
@@ -235,18 +684,30 @@ pub struct Checker {
     ast: Vec<Positioned<Node>>,
     index: usize,
     scope: Scope,
-    includes: Vec<(Positioned<()>, Positioned<String>)>
+    includes: Vec<(Positioned<()>, Positioned<String>)>,
+    modules: Vec<Arc<Mutex<ModuleSymbol>>>, // Every module checked before (C names are shared)
+    imported: Vec<String>,
+    imports: Vec<String>,
+    hoisted: Vec<Positioned<Node>>, // Statements needed before the current one (error propagation)
+    tries: usize,
+    fresh: bool // Every instance returned by the current function is new
 }
 
 impl Checker {
 
-    pub fn new(src: SourceFile, ast: Vec<Positioned<Node>>) -> Self {
+    pub fn new(src: SourceFile, ast: Vec<Positioned<Node>>, modules: Vec<Arc<Mutex<ModuleSymbol>>>) -> Self {
         Self {
             src,
             ast,
             index: 0,
             scope: Scope::new(ScopeType::Root, None),
             includes: Vec::new(),
+            modules,
+            imported: Vec::new(),
+            imports: Vec::new(),
+            hoisted: Vec::new(),
+            tries: 0,
+            fresh: false
         }
     }
 
@@ -254,6 +715,33 @@ impl Checker {
         self.src
     }
 
+    // The symbols defined by the checked file (imported symbols are not exported again)
+    pub fn module(&self) -> ModuleSymbol {
+        let mut module = ModuleSymbol::new(self.src.name.clone());
+        module.imports = self.imports.clone();
+        for function in self.scope.functions.iter() {
+            if !self.imported.contains(&function.lock().unwrap().name) {
+                module.functions.push(function.clone());
+            }
+        }
+        for class in self.scope.classes.iter() {
+            if !self.imported.contains(&class.lock().unwrap().name) {
+                module.classes.push(class.clone());
+            }
+        }
+        for alias in self.scope.aliases.iter() {
+            if !self.imported.contains(&alias.name) {
+                module.aliases.push(alias.clone());
+            }
+        }
+        for variable in self.scope.variables.iter() {
+            if !self.imported.contains(&variable.lock().unwrap().name) {
+                module.variables.push(variable.clone());
+            }
+        }
+        module
+    }
+
     fn advance(&mut self) {
         self.index += 1;
     }
@@ -262,11 +750,188 @@ impl Checker {
         self.ast.get(self.index).cloned()
     }
 
+    fn resolve_type(&mut self, data_type: &Positioned<TypeExpr>) -> DataType {
+        match &data_type.data {
+            TypeExpr::Named(name) => {
+                // Types of imported modules share the C namespace
+                if let Some((module, class)) = name.split_once('.') {
+                    let Some(module) = self.scope.get_module(module.to_string()) else {
+                        panic!("Module '{}' not imported at {}!", module, data_type.start);
+                    };
+                    let module = module.lock().unwrap();
+                    let alias = module.aliases.iter().find(|alias| alias.name == class);
+                    if let Some(alias) = alias.filter(|alias| alias.public) {
+                        return alias.data_type.clone();
+                    }
+                    if alias.is_none() && !module.class_names().iter().any(|x| x == class) {
+                        panic!("Class '{}' not found in module '{}' at {}!", class, module.name, data_type.start);
+                    }
+                    if module.is_private(class) {
+                        panic!("Class '{}' is private to module '{}' at {}!", class, module.name, data_type.start);
+                    }
+                    return DataType::Custom(class.to_string());
+                }
+                if name == "void" {
+                    return DataType::Void;
+                }
+                if let Some(number) = NumberType::from_name(name) {
+                    return DataType::Number(number);
+                }
+                if name == "Self" {
+                    let Some(class) = self.scope.class_name() else {
+                        panic!("'Self' is only allowed inside of a class at {}!", data_type.start);
+                    };
+                    return DataType::Custom(class);
+                }
+                if let Some(alias) = self.scope.get_alias(name) {
+                    return alias.data_type;
+                }
+                if self.scope.get_class(name.clone()).is_none() {
+                    if let Some(module) = self.modules.iter().find(|module| module.lock().unwrap().class_names().contains(name)) {
+                        panic!("Class '{}' at {} is not imported, import it from module '{}' first!", name, data_type.start, module.lock().unwrap().name);
+                    }
+                }
+                if self.is_opaque(&DataType::Custom(name.clone())) {
+                    panic!("Opaque type '{}' can only be used behind a pointer ('*{}') at {}!", name, name, data_type.start);
+                }
+                DataType::Custom(name.clone())
+            },
+            TypeExpr::Generic { name, params } => {
+                let params: Vec<DataType> = params.iter().map(|param| self.resolve_type(param)).collect();
+                match (name.as_str(), params.as_slice()) {
+                    ("Result", [_, DataType::Void]) => panic!("The error type of '{}' cannot be void at {}!", data_type.data, data_type.start),
+                    ("Result", [_, _]) => {},
+                    ("Result", _) => panic!("'{}' needs a value and an error type at {}!", data_type.data, data_type.start),
+                    _ => panic!("Unknown generic type '{}' at {}!", name, data_type.start)
+                }
+                DataType::Generic(name.clone(), params)
+            },
+            TypeExpr::Pointer { constant, inner } => match &inner.data {
+                TypeExpr::Named(name) if self.is_opaque(&DataType::Custom(name.clone())) => DataType::Pointer(*constant, Box::new(DataType::Custom(name.clone()))),
+                _ => DataType::Pointer(*constant, Box::new(self.resolve_type(inner)))
+            },
+            TypeExpr::Array { inner, size: Some(size) } => {
+                let size = match size.data.parse() {
+                    Ok(size) => size,
+                    Err(_) => self.array_size(size)
+                };
+                DataType::Array(Box::new(self.resolve_type(inner)), size)
+            },
+            TypeExpr::Array { inner, size: None } => DataType::Slice(Box::new(self.resolve_type(inner))),
+            TypeExpr::Optional(inner) => {
+                // Optionals are pointers which can be NULL
+                let inner_type = self.resolve_type(inner);
+                let nullable = match &inner_type {
+                    DataType::Pointer(_, _) | DataType::Function(_, _) => true,
+                    DataType::Custom(name) => !["c_char", "c_short", "c_int", "c_long", "c_float", "c_double", "c_void", "bool"].contains(&name.as_str()) && 
+                        !self.find_class(name.clone()).is_some_and(|class| class.lock().unwrap().kind == ClassKind::Struct),
+                    _ => false
+                };
+                if !nullable {
+                    panic!("Type '{}' cannot be optional at {}, only classes and pointers can!", inner_type, data_type.start);
+                }
+                DataType::Optional(Box::new(inner_type))
+            },
+            TypeExpr::Tuple(elements) => {
+                let elements: Vec<DataType> = elements.iter().map(|element| self.resolve_type(element)).collect();
+                for element in elements.iter() {
+                    self.check_tuple_element(element, &data_type.convert(()));
+                }
+                DataType::Tuple(elements)
+            },
+            TypeExpr::Function { params, return_type } => {
+                let return_type = return_type.as_ref().map_or(DataType::Void, |return_type| self.resolve_type(return_type));
+                DataType::Function(params.iter().map(|param| self.resolve_type(param)).collect(), Box::new(return_type))
+            },
+            TypeExpr::Closure { params, return_type } => {
+                DataType::Closure(params.iter().map(|param| self.resolve_type(param)).collect(), Box::new(self.resolve_type(return_type)))
+            },
+        }
+    }
+
+    // Builds the type expression of an inferred type (for the translator)
+    fn type_expr(&self, position: &Positioned<()>, data_type: &DataType) -> Positioned<TypeExpr> {
+        let boxed = |data_type: &DataType| Box::new(self.type_expr(position, data_type));
+        position.convert(match data_type {
+            DataType::Void => TypeExpr::Named("void".to_string()),
+            DataType::CDecimal => TypeExpr::Named("c_int".to_string()),
+            DataType::CFloat => TypeExpr::Named("c_double".to_string()),
+            DataType::CString => TypeExpr::Named("c_string".to_string()),
+            DataType::Number(number) => TypeExpr::Named(number.to_string()),
+            DataType::Array(inner, size) => TypeExpr::Array { inner: boxed(inner), size: Some(position.convert(size.to_string())) },
+            DataType::Slice(inner) => TypeExpr::Array { inner: boxed(inner), size: None },
+            DataType::Pointer(constant, inner) => TypeExpr::Pointer { constant: *constant, inner: boxed(inner) },
+            DataType::Optional(inner) => TypeExpr::Optional(boxed(inner)),
+            DataType::Function(params, return_type) => TypeExpr::Function { 
+                params: params.iter().map(|param| self.type_expr(position, param)).collect(), 
+                return_type: Some(boxed(return_type)) 
+            },
+            DataType::Closure(params, return_type) => TypeExpr::Closure { 
+                params: params.iter().map(|param| self.type_expr(position, param)).collect(), 
+                return_type: boxed(return_type) 
+            },
+            DataType::Generic(name, params) => TypeExpr::Generic { 
+                name: name.clone(), 
+                params: params.iter().map(|param| self.type_expr(position, param)).collect() 
+            },
+            DataType::Tuple(elements) => TypeExpr::Tuple(elements.iter().map(|element| self.type_expr(position, element)).collect()),
+            DataType::ResultValue(_, _) => panic!("Cannot infer the Result type of '{}' at {}!", data_type, position.start),
+            DataType::Null |
+            DataType::None => TypeExpr::Pointer { constant: false, inner: Box::new(position.convert(TypeExpr::Named("c_void".to_string()))) },
+            DataType::Custom(name) => TypeExpr::Named(name.clone()),
+        })
+    }
+
     fn check_data_type(&mut self, expected: DataType, found: DataType) -> bool {
-        // TODO: Check if it can be explicitly casted (later)
+        // Explicit conversions are checked by check_cast
         if expected == found {
             return true;
         }
+        match (&expected, &found) {
+            (DataType::Array(expected, expected_size), DataType::Array(found, found_size)) => {
+                return expected_size == found_size && self.check_data_type(*expected.clone(), *found.clone());
+            },
+            (DataType::Slice(expected), DataType::Slice(found)) |
+            (DataType::Slice(expected), DataType::Array(found, _)) => {
+                return self.check_data_type(*expected.clone(), *found.clone());
+            },
+            (DataType::Tuple(expected), DataType::Tuple(found)) => {
+                return expected.len() == found.len() && 
+                    expected.iter().zip(found).all(|(expected, found)| self.check_data_type(expected.clone(), found.clone()));
+            },
+            (DataType::Function(expected_params, expected_return), DataType::Function(found_params, found_return)) |
+            (DataType::Closure(expected_params, expected_return), DataType::Closure(found_params, found_return)) => {
+                return expected_params.len() == found_params.len() && 
+                    expected_params.iter().zip(found_params).all(|(expected, found)| expected.normalized() == found.normalized()) &&
+                    expected_return.normalized() == found_return.normalized();
+            },
+            (DataType::Generic(name, params), DataType::ResultValue(ok, found)) if name == "Result" => {
+                let expected = if *ok { &params[0] } else { &params[1] };
+                return self.check_data_type(expected.clone(), *found.clone());
+            },
+            (_, DataType::ResultValue(_, _)) => return false,
+            (DataType::Optional(_), DataType::None) => return true,
+            (DataType::Optional(expected), DataType::Optional(found)) => return self.check_data_type(*expected.clone(), *found.clone()),
+            (DataType::Optional(expected), _) => return self.check_data_type(*expected.clone(), found.clone()),
+            (_, DataType::Optional(_) | DataType::None) => return false,
+            (DataType::Pointer(_, _) | DataType::Function(_, _), DataType::Null) => return true,
+            (DataType::Pointer(expected_constant, expected), DataType::Pointer(found_constant, found)) => {
+                // A pointer can become const but not the opposite
+                if *found_constant && !*expected_constant {
+                    return false;
+                }
+                return expected.is_named("c_void") || found.is_named("c_void") || expected.normalized() == found.normalized();
+            },
+            (DataType::Pointer(_, expected), _) if found.is_named("c_string") => return expected.is_named("c_char"),
+            (_, DataType::Pointer(false, found)) if expected.is_named("c_string") => return found.is_named("c_char"),
+            (DataType::Pointer(_, _), _) | (_, DataType::Pointer(_, _)) | (_, DataType::Null) => return false,
+            (DataType::Number(expected), DataType::Number(found)) => return found.converts_to(expected),
+            // Literals fit any number (their range is checked when folded)
+            (DataType::Number(_), DataType::CDecimal) | (DataType::CDecimal, DataType::Number(_)) => return true,
+            (DataType::Number(number), DataType::CFloat) | (DataType::CFloat, DataType::Number(number)) => return number.is_float(),
+            (DataType::Number(_), _) | (_, DataType::Number(_)) => return false,
+            _ => {}
+        }
         match expected {
             DataType::CDecimal => {
                 if let DataType::Custom(inner) = &found {
@@ -306,6 +971,7 @@ impl Checker {
                     false
                 }
             },
+            DataType::CFloat => matches!(expected, DataType::Custom(inner) if inner == "c_float" || inner == "c_double"),
             DataType::CString => {
                 if let DataType::Custom(inner) = expected {
                     if inner == "c_string" {
@@ -322,8 +988,10 @@ impl Checker {
         if let Some(data_type) = node_info.data_type.clone() {
             if self.check_data_type(other.clone(), data_type.clone()) {
                 other
+            } else if let (DataType::Closure(_, _), DataType::Function(_, _)) = (&data_type, &other) {
+                panic!("A capturing lambda cannot be used as the function pointer '{}', take a '{}' instead!", other, data_type);
             } else {
-                panic!("Unexpected type '{:?}', should be '{:?}'", data_type, other); 
+                panic!("Unexpected type '{}', should be '{}'", data_type, other);
             }
         } else if let Some(variable) = node_info.symbol {
             match variable {
@@ -356,31 +1024,248 @@ impl Checker {
         }
     }
 
+    fn initializer_mismatch(&self, name: &Positioned<String>, value: &Positioned<Node>, expected: &DataType, found: &DataType) -> ! {
+        // The first (possibly nested) array length that differs
+        let (mut expected_array, mut found_array) = (expected, found);
+        while let (DataType::Array(expected_inner, expected_size), DataType::Array(found_inner, found_size)) = (expected_array, found_array) {
+            if expected_size != found_size {
+                panic!("Cannot initialize '{}' of type '{}' with '{}' at {}, expected {} elements but found {}!", name.data, expected, value.data, value.start, expected_size, found_size);
+            }
+            (expected_array, found_array) = (expected_inner, found_inner);
+        }
+
+        // Numbers are only converted to bool by casts
+        if expected.is_named("bool") && found.is_numeric() {
+            panic!("Cannot use '{}' of type '{}' as 'bool' at {}, use 'as bool'!", value.data, found, value.start);
+        }
+
+        let reason = match (expected, found) {
+            (DataType::Number(expected), DataType::Number(found)) if found.is_float() && !expected.is_float() => ", it would lose the fraction, use a cast",
+            (expected, DataType::CFloat) if expected.is_numeric() => ", it would lose the fraction, use a cast",
+            (DataType::Number(expected), DataType::Number(found)) if found.is_signed() != expected.is_signed() => ", it can change the sign, use a cast",
+            _ => ""
+        };
+        panic!("Cannot initialize '{}' of type '{}' with '{}' of type '{}' at {}{}!", name.data, expected, value.data, found, value.start, reason);
+    }
+
+    // Lowers the implicit conversions accepted by check_data_type
+    fn coerce(&mut self, node: Positioned<Node>, found: Option<DataType>, expected: &DataType) -> Positioned<Node> {
+        match (expected, found) {
+            (DataType::Number(expected), Some(DataType::Number(found))) => {
+                if found.is_lossy(expected) {
+                    self.warn(&node.convert(()), format!("Lossy implicit conversion of '{}' from '{}' to '{}', use a cast", node.data, found, expected));
+                }
+                node
+            },
+            (DataType::Slice(element), Some(DataType::Array(_, size))) => {
+                // Array literals take the element type of the slice
+                let value = match node.data.clone() {
+                    Node::Array { data_type: _, values } => node.convert(Node::Array { 
+                        data_type: Some(self.type_expr(&node.convert(()), &DataType::Array(element.clone(), size))), 
+                        values 
+                    }),
+                    _ => node
+                };
+
+                value.convert(Node::Slice { 
+                    data_type: self.type_expr(&value.convert(()), expected), 
+                    value: Box::new(value.clone()), 
+                    length: Box::new(value.convert(Node::Value(ValueNode::Decimal(size.to_string())))) 
+                })
+            },
+            (DataType::Generic(_, params), Some(DataType::ResultValue(ok, found))) => {
+                // The Result literal gets its C type
+                let Node::Result { ok: _, value, .. } = node.data.clone() else {
+                    return node;
+                };
+                let inner = if ok { &params[0] } else { &params[1] };
+                let value = value.map(|value| Box::new(self.coerce(*value, Some(*found), inner)));
+                node.convert(Node::Result { data_type: Some(self.type_expr(&node.convert(()), expected)), ok, value })
+            },
+            (DataType::Tuple(elements), Some(DataType::Tuple(found))) => {
+                // The tuple literal gets the C type of the expected tuple
+                let Node::Tuple { data_type: _, values } = node.data.clone() else {
+                    return node;
+                };
+                let values = values.into_iter().zip(found).zip(elements.iter()).map(|((value, found), element)| self.coerce(value, Some(found), element)).collect();
+                node.convert(Node::Tuple { data_type: Some(self.type_expr(&node.convert(()), expected)), values })
+            },
+            _ => node
+        }
+    }
+
+    fn warn(&self, position: &Positioned<()>, message: String) {
+        println!("[Checker]: Warning: {} at {} in {}.apla\n{}", message, position.start, self.src.name, position.arrow_message(&self.src.src));
+    }
+
+    // Values can have the type of a class from any module whose header is included
+    fn find_class(&mut self, name: String) -> Option<Arc<Mutex<ClassSymbol>>> {
+        if let Some(class) = self.scope.get_class(name.clone()) {
+            return Some(class);
+        }
+        for module in self.visible_modules() {
+            if let Some(class) = module.lock().unwrap().classes.iter().find(|x| x.lock().unwrap().name == name) {
+                return Some(class.clone());
+            }
+        }
+        None
+    }
+
+    // The imported modules and the modules they import
+    fn visible_modules(&self) -> Vec<Arc<Mutex<ModuleSymbol>>> {
+        let mut names = self.imports.clone();
+        let mut visible: Vec<Arc<Mutex<ModuleSymbol>>> = Vec::new();
+        while let Some(name) = names.pop() {
+            let Some(module) = self.modules.iter().find(|module| module.lock().unwrap().name == name) else {
+                continue;
+            };
+            if visible.iter().any(|x| Arc::ptr_eq(x, module)) {
+                continue;
+            }
+            names.extend(module.lock().unwrap().imports.clone());
+            visible.push(module.clone());
+        }
+        visible
+    }
+
+    // Public functions, globals and classes of all modules share the C namespace
+    fn check_linked_name(&self, name: &Positioned<String>) {
+        for module in self.modules.iter() {
+            let module = module.lock().unwrap();
+            let function = module.functions.iter().any(|function| {
+                let function = function.lock().unwrap();
+                function.public && function.name == name.data && !matches!(function.function_type, FunctionType::ExternalFunction)
+            });
+            let variable = module.variables.iter().any(|variable| {
+                let variable = variable.lock().unwrap();
+                variable.public && variable.name == name.data
+            });
+            let class = module.classes.iter().any(|class| {
+                let class = class.lock().unwrap();
+                class.kind == ClassKind::Class && class.name == name.data
+            });
+            if function || variable || class {
+                panic!("'{}' at {} is already defined by module '{}', public names are shared in C!", name.data, name.start, module.name);
+            }
+        }
+    }
+
+    fn is_opaque(&mut self, data_type: &DataType) -> bool {
+        match data_type {
+            DataType::Custom(name) => self.find_class(name.clone()).is_some_and(|class| class.lock().unwrap().kind == ClassKind::Opaque),
+            _ => false
+        }
+    }
+
+    // Name of the symbol accessed by a member access
+    fn member_name(node: &Node) -> Option<String> {
+        match node {
+            Node::Value(ValueNode::VariableCall(name)) => Some(name.clone()),
+            Node::FunctionCall { name, .. } => Some(name.data.clone()),
+            _ => None
+        }
+    }
+
+    fn field_access(value: Positioned<Node>, field: &str) -> Positioned<Node> {
+        value.convert(Node::BinaryOperation { 
+            lhs: Box::new(value.clone()), 
+            op: value.convert(Operator::FieldAccess), 
+            rhs: Box::new(value.convert(Node::Value(ValueNode::VariableCall(field.to_string())))) 
+        })
+    }
+
     fn check_value(&mut self, value_node: Positioned<ValueNode>) -> (NodeInfo, Vec<Positioned<Node>>) {
         match value_node.data.clone() {
             ValueNode::Decimal(_) => (
                 NodeInfo::new(Some(DataType::CDecimal), None), 
                 vec![value_node.convert(Node::Value(value_node.data.clone()))]
             ),
+            ValueNode::Float(_) => (
+                NodeInfo::new(Some(DataType::CFloat), None), 
+                vec![value_node.convert(Node::Value(value_node.data.clone()))]
+            ),
             ValueNode::String(_) => (
                 NodeInfo::new(Some(DataType::CString), None), 
                 vec![value_node.convert(Node::Value(value_node.data.clone()))]
             ),
+            ValueNode::Bool(_) => (
+                NodeInfo::new(Some(DataType::Custom("bool".to_string())), None), 
+                vec![value_node.convert(Node::Value(value_node.data.clone()))]
+            ),
             ValueNode::VariableCall(value) => {
-                if let Some(variable) = self.scope.get_variable(value.clone()) {
-                    (NodeInfo::new(variable.lock().unwrap().data_type.clone(), Some(Symbol::Variable(variable.clone()))), vec![
+                // Member lookups only happen in the selected scope
+                let mut selected = self.scope.selected.take();
+                let member = selected.is_some();
+                let method = selected.as_ref().is_some_and(|selected| matches!(selected.scope, ScopeType::Class(_)));
+                let scope = match &mut selected {
+                    Some(selected) => selected.as_mut(),
+                    None => &mut self.scope
+                };
+                let variable = scope.get_variable(value.clone());
+                let class = scope.get_class(value.clone());
+                let function = scope.get_function(value.clone());
+                let module = scope.get_module(value.clone());
+
+                if let Some(variable) = variable {
+                    let mut data_type = variable.lock().unwrap().data_type.clone();
+                    if !member && variable.lock().unwrap().moved {
+                        panic!("Cannot use '{}' at {}, its instance has been moved!", value, value_node.start);
+                    }
+                    if !member {
+                        self.capture(&value_node.convert(()), &variable);
+
+                        // Checked against none by an enclosing if
+                        if let Some(DataType::Optional(inner)) = &data_type {
+                            if self.scope.is_narrowed(&variable) {
+                                data_type = Some(*inner.clone());
+                            }
+                        }
+                    }
+                    (NodeInfo::new(data_type, Some(Symbol::Variable(variable.clone()))), vec![
                         value_node.convert(Node::Value(ValueNode::VariableCall(value.clone())))
                     ])
-                } else if let Some(class) = self.scope.get_class(value.clone()) {
+                } else if let Some(class) = class {
                     (NodeInfo::new(Some(DataType::Custom(value.clone())), Some(Symbol::Class(class))), vec![
                         value_node.convert(Node::Value(ValueNode::VariableCall(value.clone())))
                     ])
-                } else {
+                } else if let Some(function) = function {
+                    // Functions used as values are C function pointers
+                    let function = function.lock().unwrap().clone();
+                    if method {
+                        panic!("Cannot use method '{}' as a value at {}!", value, value_node.start);
+                    }
+                    if let FunctionType::Constructor = function.function_type {
+                        panic!("Cannot use constructor '{}' as a value at {}!", value, value_node.start);
+                    }
+                    if function.variadic {
+                        panic!("Cannot use variadic function '{}' as a value at {}!", value, value_node.start);
+                    }
+                    let params = function.params.clone();
+                    (NodeInfo::new(Some(DataType::Function(params, Box::new(function.data_type))), None), vec![
+                        value_node.convert(Node::Value(ValueNode::VariableCall(value.clone())))
+                    ])
+                } else if let Some(module) = module {
+                    (NodeInfo::new(None, Some(Symbol::Module(module))), vec![
+                        value_node.convert(Node::Value(ValueNode::VariableCall(value.clone())))
+                    ])
+                } else {
                     panic!("Variable / Class '{}' not found", value)
                 }
             }
+            ValueNode::Null => (
+                NodeInfo::new(Some(DataType::Null), None), 
+                vec![value_node.convert(Node::Value(ValueNode::Null))]
+            ),
+            ValueNode::None => (
+                NodeInfo::new(Some(DataType::None), None), 
+                vec![value_node.convert(Node::Value(ValueNode::None))]
+            ),
             ValueNode::This => {
+                if self.scope.is_static() {
+                    panic!("Cannot use self inside of a static function at {}!", value_node.start);
+                }
                 if let Some(this) = self.scope.get_variable("self".to_string()) {
+                    self.capture(&value_node.convert(()), &this);
                     let data_type = this.lock().unwrap().data_type.clone();
                     (NodeInfo::new(data_type, Some(Symbol::Variable(this))), vec![
                         value_node.convert(Node::Value(ValueNode::This))
@@ -392,32 +1277,125 @@ impl Checker {
         }
     }
 
+    // Variables used inside of a lambda are copied into its environment
+    fn capture(&mut self, position: &Positioned<()>, variable: &Arc<Mutex<VariableSymbol>>) {
+        if !self.scope.capture(variable) || !self.scope.is_captured(variable) {
+            return;
+        }
+
+        let variable = variable.lock().unwrap();
+        match &variable.data_type {
+            Some(DataType::Array(_, _)) => panic!("Cannot capture the fixed-size array '{}' at {}, capture a slice instead!", variable.name, position.start),
+            None => panic!("Cannot infer the type of the captured variable '{}' at {}!", variable.name, position.start),
+            _ if !variable.initialized => panic!("Cannot capture the uninitialized variable '{}' at {}!", variable.name, position.start),
+            _ => {}
+        }
+    }
+
     fn check_assignment(&mut self, position: Positioned<()>, lhs: Positioned<Node>, op: Positioned<Operator>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        // A moved variable can get a new instance (but is still moved in the value)
+        let moved = match &lhs.data {
+            Node::Value(ValueNode::VariableCall(name)) => self.scope.get_variable(name.clone()).filter(|variable| variable.lock().unwrap().moved),
+            _ => None
+        };
+        if let Some(variable) = &moved {
+            variable.lock().unwrap().moved = false;
+        }
         let (lhs_info, lhs_ast) = self.check_node(lhs);
+        if let Some(variable) = &moved {
+            variable.lock().unwrap().moved = true;
+        }
         let (rhs_info, rhs_ast) = self.check_node(rhs);
+
+        if let Some(DataType::Array(_, _)) = lhs_info.data_type {
+            panic!("Cannot assign to fixed-size array, assign its elements instead!");
+        }
+        // The environment of a lambda only lives as long as the block creating it
+        if let Some(DataType::Closure(_, _)) = lhs_info.data_type {
+            let current = match &lhs_info.symbol {
+                Some(Symbol::Variable(variable)) => self.scope.variables.iter().any(|x| Arc::ptr_eq(x, variable)),
+                _ => false
+            };
+            if !current {
+                panic!("Cannot assign a capturing lambda to '{}' at {}, it is not declared in the current block!", lhs_ast[0].data, position.start);
+            }
+        }
         
         // Check var type
         if let Some(symbol) = &lhs_info.symbol {
             match symbol {
                 Symbol::Function(_) => panic!("Cannot assign to functions!"),
                 Symbol::Variable(variable) => {
+                    if self.scope.is_captured(variable) {
+                        panic!("Cannot assign to '{}' at {}, it is captured by value!", variable.lock().unwrap().name, position.start);
+                    }
                     let mut variable = variable.lock().unwrap();
                     if variable.var_type == VarType::Constant && variable.initialized {
-                        panic!("Cannot assign to constant '{:?}'!", variable);
+                        if let Node::UnaryOperation { op: Positioned { data: UnaryOperator::Dereference, .. }, value } = &lhs_ast[0].data {
+                            panic!("Cannot assign through '{}' at {}, it points to constant data!", value.data, position.start);
+                        }
+                        panic!("Cannot assign to constant '{}' at {}!", variable.name, position.start);
                     }
                     variable.initialized = true;
                 },
                 Symbol::Class(_) => panic!("Cannot assign to classes"),
+                Symbol::Module(_) => panic!("Cannot assign to modules"),
             }
         }
 
+        // Narrowing only applies to reads, the variable still accepts optionals
+        let narrowed = match (&lhs_info.symbol, &lhs_ast[0].data) {
+            (Some(Symbol::Variable(variable)), Node::Value(ValueNode::VariableCall(_))) if self.scope.is_narrowed(variable) => Some(variable.clone()),
+            _ => None
+        };
+
         // Check data_type
-        let data_type = self.infer_and_check2(lhs_info, rhs_info);
+        let symbol = lhs_info.symbol.clone();
+        let expected = match &narrowed {
+            Some(variable) => variable.lock().unwrap().data_type.clone(),
+            None => lhs_info.data_type.clone()
+        };
+        let found = rhs_info.data_type.clone();
+        if let Some(variable) = &narrowed {
+            if matches!(found, Some(DataType::Optional(_) | DataType::None)) {
+                self.scope.unnarrow(variable);
+            }
+        }
+        let data_type = match expected.clone() {
+            Some(expected) => self.infer_and_check(rhs_info, expected),
+            None => self.infer_and_check2(lhs_info, rhs_info)
+        };
+        let mut rhs_node = self.coerce(rhs_ast[0].clone(), found, &expected.unwrap_or(data_type.clone())); // TODO: check if more than 1 node
+
+        // Reassigned instances are freed (after computing the new value)
+        let class = self.is_class(&Some(data_type.clone()));
+        match (symbol, class) {
+            (Some(Symbol::Variable(variable)), Some(class)) if self.scope.is_local(&variable) => {
+                let owned = self.is_owning_value(&rhs_ast[0]);
+                let mut symbol = variable.lock().unwrap();
+                // The other paths keep the previous instance
+                let inner = !self.scope.variables.iter().any(|x| Arc::ptr_eq(x, &variable));
+                if inner && (symbol.owned != owned || symbol.moved) {
+                    panic!("Cannot change the ownership of '{}' in an enclosing scope at {}!", symbol.name, position.start);
+                }
+                if symbol.owned {
+                    rhs_node = position.convert(Node::FunctionCall { 
+                        name: position.convert(format!("{}_replace", class)), 
+                        params: vec![FunctionCallParameter { value: lhs_ast[0].clone() }, FunctionCallParameter { value: rhs_node }] 
+                    });
+                }
+                symbol.owned = owned;
+                symbol.moved = false;
+            },
+            (_, Some(_)) => _ = self.moved_variable(&rhs_ast[0]), // Given to the object
+            _ => {}
+        }
+
         (NodeInfo::new(Some(data_type), None), vec![
             position.convert(Node::BinaryOperation {
                 lhs: Box::new(lhs_ast[0].clone()), // TODO: check if more than 1 node
                 op,
-                rhs: Box::new(rhs_ast[0].clone()) // TODO: check if more than 1 node
+                rhs: Box::new(rhs_node)
             })
         ])
     }
@@ -425,23 +1403,54 @@ impl Checker {
     fn check_member_access(&mut self, position: Positioned<()>, lhs: Positioned<Node>, op: Positioned<Operator>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
         let (lhs_info, lhs_ast) = self.check_node(lhs);
 
-        if let Some(symbol) = lhs_info.symbol {
-            let class_symbol = match symbol {
-                Symbol::Function(_) => panic!("Access impossible in function"),
-                Symbol::Variable(variable) => {
-                    if let Some(DataType::Custom(data_type)) = variable.lock().unwrap().data_type.clone() {
-                        if let Some(class_symbol) = self.scope.get_class(data_type) {
-                            class_symbol
-                        } else {
-                            panic!("Could not get class!")
-                        }
+        if let Some(data_type @ (DataType::Array(_, _) | DataType::Slice(_))) = lhs_info.data_type {
+            return self.check_length(position, lhs_ast[0].clone(), data_type, rhs);
+        }
+        if let Some(DataType::Generic(_, params)) = lhs_info.data_type {
+            return self.check_result_member(lhs_ast[0].clone(), params, rhs);
+        }
+        if let Some(DataType::Tuple(elements)) = lhs_info.data_type.clone() {
+            return self.check_tuple_access(position, lhs_info, lhs_ast[0].clone(), elements, rhs);
+        }
+
+        if let Some(data_type @ DataType::Optional(_)) = &lhs_info.data_type {
+            panic!("Cannot access a member of '{}' of type '{}' at {}, check it against none first!", lhs_ast[0].data, data_type, position.start);
+        }
+
+        if lhs_info.symbol.is_some() || lhs_info.data_type.is_some() {
+            let instance = !matches!(lhs_info.symbol, Some(Symbol::Class(_)));
+            let class_symbol = match (lhs_info.symbol, lhs_info.data_type) {
+                (Some(Symbol::Function(_)), _) => panic!("Access impossible in function"),
+                (Some(Symbol::Module(module)), _) => return self.check_module_access(module, rhs),
+                (Some(Symbol::Class(class)), _) => class,
+                (_, Some(DataType::Custom(data_type))) => {
+                    if let Some(class_symbol) = self.find_class(data_type) {
+                        class_symbol
                     } else {
-                        panic!("Could not infer type of variable!")
+                        panic!("Could not get class!")
                     }
                 },
-                Symbol::Class(class) => class,
+                _ => panic!("Could not infer type of variable!")
             };
 
+            // Private members are only visible inside of the class
+            let class = class_symbol.lock().unwrap().clone();
+            if let Some(member) = Self::member_name(&rhs.data) {
+                if class.is_private(&member) && !self.scope.in_class(&class.name) {
+                    panic!("'{}' is private to class '{}' at {}!", member, class.name, rhs.start);
+                }
+            }
+
+            // Static fields are globals prefixed by the class
+            if let (false, Node::Value(ValueNode::VariableCall(member))) = (instance, &rhs.data) {
+                if let Some(variable) = class.statics.iter().find(|x| &x.lock().unwrap().name == member) {
+                    let data_type = variable.lock().unwrap().data_type.clone();
+                    return (NodeInfo::new(data_type, Some(Symbol::Variable(variable.clone()))), vec![
+                        rhs.convert(Node::Value(ValueNode::VariableCall(format!("{}_{}", class.name, member))))
+                    ]);
+                }
+            }
+
             // Select the scope
             let mut scope = Box::new(Scope::new(ScopeType::Class(class_symbol.lock().unwrap().name.clone()), None));
 
@@ -458,8 +1467,32 @@ impl Checker {
             self.scope.selected = Some(scope);
 
             // Process rhs
-            let (rhs_info, rhs_ast) = self.check_node(rhs);
+            let (rhs_info, rhs_ast) = self.check_node(rhs.clone());
+
+            // Methods are functions taking the instance first
+            if let Node::FunctionCall { name, params } = rhs_ast[0].data.clone() {
+                let method = Self::member_name(&rhs.data).and_then(|member| class.functions.iter().find(|x| x.lock().unwrap().name == member).cloned());
+                if let Some(method) = method {
+                    let function_type = method.lock().unwrap().function_type.clone();
+                    return match (function_type, instance) {
+                        (FunctionType::Constructor | FunctionType::Static, false) => (rhs_info, rhs_ast),
+                        (FunctionType::Static, true) => panic!("Static function '{}' is called on the class '{}' at {}!", method.lock().unwrap().name, class.name, rhs.start),
+                        (FunctionType::Constructor, true) => panic!("Cannot call constructor '{}' on an instance at {}!", name.data, rhs.start),
+                        (_, false) => panic!("Method '{}' needs an instance of '{}' at {}!", name.data, class.name, rhs.start),
+                        _ => {
+                            let mut new_params = vec![FunctionCallParameter { value: lhs_ast[0].clone() }];
+                            new_params.extend(params);
+                            (rhs_info, vec![position.convert(Node::FunctionCall { name, params: new_params })])
+                        }
+                    };
+                }
+            }
             
+            // Structs are values
+            let op = match class.kind {
+                ClassKind::Struct => op.convert(Operator::FieldAccess),
+                _ => op
+            };
             (rhs_info, vec![
                 position.convert(Node::BinaryOperation { 
                     lhs: Box::new(lhs_ast[0].clone()), 
@@ -472,88 +1505,497 @@ impl Checker {
         }
     }
 
+    fn check_module_access(&mut self, module: Arc<Mutex<ModuleSymbol>>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let module = module.lock().unwrap().clone();
+        if let Some(member) = Self::member_name(&rhs.data) {
+            if module.is_private(&member) {
+                panic!("'{}' is private to module '{}' at {}!", member, module.name, rhs.start);
+            }
+        }
+
+        // Select the scope
+        let mut scope = Box::new(Scope::new(ScopeType::Module(module.name.clone()), None));
+        scope.functions = module.functions;
+        scope.classes = module.classes;
+        scope.variables = module.variables;
+        self.scope.selected = Some(scope);
+
+        // The module disappears in C (single namespace)
+        self.check_node(rhs)
+    }
+
+    fn check_length(&mut self, position: Positioned<()>, value: Positioned<Node>, data_type: DataType, member: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        match &member.data {
+            Node::Value(ValueNode::VariableCall(name)) if name == "len" => {},
+            _ => panic!("Unknown member '{}' of '{:?}'", member.data, data_type)
+        }
+
+        // Slices store their length as a size_t
+        let (length, length_type) = match data_type {
+            DataType::Array(_, size) => (position.convert(Node::Value(ValueNode::Decimal(size.to_string()))), DataType::CDecimal),
+            _ => (Self::field_access(value, "len"), DataType::Number(NumberType::Usize))
+        };
+
+        (NodeInfo::new(Some(length_type), None), vec![length])
+    }
+
+    fn check_result_member(&mut self, value: Positioned<Node>, params: Vec<DataType>, member: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let data_type = match &member.data {
+            Node::Value(ValueNode::VariableCall(name)) if name == "ok" => DataType::CDecimal,
+            Node::Value(ValueNode::VariableCall(name)) if name == "value" && params[0] != DataType::Void => params[0].clone(),
+            Node::Value(ValueNode::VariableCall(name)) if name == "error" => params[1].clone(),
+            _ => panic!("Unknown member '{}' of 'Result' at {}!", member.data, member.start)
+        };
+        let Some(field) = Self::member_name(&member.data) else {
+            unreachable!()
+        };
+        (NodeInfo::new(Some(data_type), None), vec![Self::field_access(value, &field)])
+    }
+
+    // Numbers convert to each other, pointers only in unsafe code
+    fn check_cast(&mut self, position: Positioned<()>, value: Positioned<Node>, data_type: Positioned<TypeExpr>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (value_info, value_ast) = self.check_node(value.clone());
+        let Some(found) = value_info.data_type else {
+            panic!("Could not infer the type of '{}' at {}!", value.data, value.start);
+        };
+        let target = self.resolve_type(&data_type);
+
+        let is_pointer = |checker: &mut Self, data_type: &DataType| match data_type {
+            DataType::Pointer(_, _) | DataType::Optional(_) | DataType::Function(_, _) | DataType::Null => true,
+            _ => data_type.is_named("c_string") || checker.is_class(&Some(data_type.clone())).is_some()
+        };
+        let implicit = self.check_data_type(target.clone(), found.clone());
+        let numeric = found.is_numeric() && target.is_numeric();
+        // Integers and bools only convert explicitly
+        let boolean = (found.is_named("bool") && target.is_numeric()) || (found.is_numeric() && target.is_named("bool"));
+        if !implicit && !numeric && !boolean {
+            if !is_pointer(self, &found) || !is_pointer(self, &target) {
+                panic!("Invalid cast of '{}' from '{}' to '{}' at {}!", value.data, found, target, position.start);
+            }
+            if !self.scope.is_unsafe() {
+                panic!("Cannot cast '{}' from '{}' to '{}' at {} outside of an unsafe block!", value.data, found, target, position.start);
+            }
+        }
+
+        (NodeInfo::new(Some(target), None), vec![
+            position.convert(Node::Cast { value: Box::new(value_ast[0].clone()), data_type })
+        ])
+    }
+
     fn check_binary_operation(&mut self, position: Positioned<()>, lhs: Positioned<Node>, op: Positioned<Operator>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {        
         // TODO: Check binary operation
 
         match op.data {
-            Operator::Plus => todo!("Check plus"),
-            Operator::Minus => todo!("Check minus"),
-            Operator::Multiply => todo!("Check multiply"),
-            Operator::Divide => todo!("Check divide"),
+            Operator::Plus |
+            Operator::Minus |
+            Operator::Multiply |
+            Operator::Divide => self.check_arithmetic(position, lhs, op, rhs),
             Operator::MemberAccess => self.check_member_access(position, lhs, op, rhs),
+            Operator::FieldAccess => panic!("Field access is only generated by the checker!"),
+            Operator::Equal |
+            Operator::NotEqual |
+            Operator::Less |
+            Operator::LessEqual |
+            Operator::Greater |
+            Operator::GreaterEqual => self.check_comparison(position, lhs, op, rhs),
+            Operator::And |
+            Operator::Or => self.check_logical(position, lhs, op, rhs),
             Operator::Assignment => self.check_assignment(position, lhs, op, rhs),
         }
     }
 
-    fn check_variable_definition(&mut self, position: Positioned<()>, var_type: Positioned<VarType>, name: Positioned<String>, data_type: Option<Positioned<String>>, value: Option<Box<Positioned<Node>>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+    fn check_arithmetic(&mut self, position: Positioned<()>, lhs: Positioned<Node>, op: Positioned<Operator>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (lhs_info, lhs_ast) = self.check_node(lhs);
+        let (rhs_info, rhs_ast) = self.check_node(rhs);
+
+        let (Some(lhs_type), Some(rhs_type)) = (lhs_info.data_type, rhs_info.data_type) else {
+            panic!("Cannot infer the types of the operation at {}!", position.start);
+        };
+        if let Some(result) = self.check_operator_method(&position, &op, (lhs_ast[0].clone(), lhs_type.clone()), (rhs_ast[0].clone(), rhs_type.clone())) {
+            return result;
+        }
+        for data_type in [&lhs_type, &rhs_type] {
+            if !data_type.is_numeric() {
+                panic!("Cannot use '{}' in an arithmetic operation at {}!", data_type, position.start);
+            }
+        }
+
+        // Literals take the type of the other operand
+        let data_type = match (lhs_type, rhs_type) {
+            (DataType::CDecimal, data_type) | (data_type, DataType::CDecimal) => data_type,
+            (DataType::CFloat, data_type) | (data_type, DataType::CFloat) if self.check_data_type(data_type.clone(), DataType::CFloat) => data_type,
+            (lhs_type, rhs_type) if lhs_type == rhs_type => lhs_type,
+            (lhs_type, rhs_type) => panic!("Mismatched types '{}' and '{}' at {}!", lhs_type, rhs_type, position.start)
+        };
+
+        (NodeInfo::new(Some(data_type), None), vec![
+            position.convert(Node::BinaryOperation { 
+                lhs: Box::new(lhs_ast[0].clone()), 
+                op, 
+                rhs: Box::new(rhs_ast[0].clone()) 
+            })
+        ])
+    }
+
+    fn check_comparison(&mut self, position: Positioned<()>, lhs: Positioned<Node>, op: Positioned<Operator>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (lhs_info, lhs_ast) = self.check_node(lhs);
+        let (rhs_info, rhs_ast) = self.check_node(rhs);
+
+        let (Some(lhs_type), Some(rhs_type)) = (lhs_info.data_type, rhs_info.data_type) else {
+            panic!("Cannot infer the types of the comparison at {}!", position.start);
+        };
+        if let Some(result) = self.check_operator_method(&position, &op, (lhs_ast[0].clone(), lhs_type.clone()), (rhs_ast[0].clone(), rhs_type.clone())) {
+            return result;
+        }
+        for data_type in [&lhs_type, &rhs_type] {
+            if let DataType::Array(_, _) | DataType::Slice(_) | DataType::Closure(_, _) | DataType::Generic(_, _) | DataType::ResultValue(_, _) = data_type {
+                panic!("Cannot compare '{}' at {}, compare its elements instead!", data_type, position.start);
+            }
+            if let DataType::Custom(name) = data_type {
+                if self.find_class(name.clone()).is_some_and(|class| class.lock().unwrap().kind == ClassKind::Struct) {
+                    panic!("Cannot compare the struct '{}' at {}, compare its fields instead!", data_type, position.start);
+                }
+            }
+        }
+        // Only numbers are ordered
+        if !matches!(op.data, Operator::Equal | Operator::NotEqual) {
+            for data_type in [&lhs_type, &rhs_type] {
+                if !data_type.is_numeric() {
+                    panic!("Cannot order '{}' at {}!", data_type, position.start);
+                }
+            }
+        }
+        if !self.check_data_type(lhs_type.clone(), rhs_type.clone()) && !self.check_data_type(rhs_type.clone(), lhs_type.clone()) {
+            panic!("Cannot compare '{}' with '{}' at {}!", lhs_type, rhs_type, position.start);
+        }
+
+        (NodeInfo::new(Some(DataType::Custom("bool".to_string())), None), vec![
+            position.convert(Node::BinaryOperation { 
+                lhs: Box::new(lhs_ast[0].clone()), 
+                op, 
+                rhs: Box::new(rhs_ast[0].clone()) 
+            })
+        ])
+    }
+
+    fn check_logical(&mut self, position: Positioned<()>, lhs: Positioned<Node>, op: Positioned<Operator>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (lhs_info, lhs_ast) = self.check_node(lhs.clone());
+        let (rhs_info, rhs_ast) = self.check_node(rhs.clone());
+
+        for (value, data_type) in [(&lhs, lhs_info.data_type), (&rhs, rhs_info.data_type)] {
+            if !data_type.as_ref().is_some_and(|data_type| data_type.is_named("bool")) {
+                panic!("Expected 'bool' but found '{}' of type '{}' at {}!", value.data, data_type.map_or("unknown".to_string(), |x| x.to_string()), value.start);
+            }
+        }
+
+        (NodeInfo::new(Some(DataType::Custom("bool".to_string())), None), vec![
+            position.convert(Node::BinaryOperation { 
+                lhs: Box::new(lhs_ast[0].clone()), 
+                op, 
+                rhs: Box::new(rhs_ast[0].clone()) 
+            })
+        ])
+    }
+
+    // Operators on class instances call their methods: a + b => A_add(a, b)
+    // The missing comparisons are derived from eq and lt (a > b => b < a, a >= b => !(a < b), ...)
+    fn check_operator_method(&mut self, position: &Positioned<()>, op: &Positioned<Operator>, lhs: (Positioned<Node>, DataType), rhs: (Positioned<Node>, DataType)) -> Option<(NodeInfo, Vec<Positioned<Node>>)> {
+        let DataType::Custom(class_name) = &lhs.1 else {
+            return None;
+        };
+        let class = self.find_class(class_name.clone())?.lock().unwrap().clone();
+        // Identity comparisons with null / none
+        if let DataType::Null | DataType::None = rhs.1 {
+            return None;
+        }
+
+        let (name, fallback) = match op.data {
+            Operator::Plus => ("add", None),
+            Operator::Minus => ("sub", None),
+            Operator::Multiply => ("mul", None),
+            Operator::Divide => ("div", None),
+            Operator::Equal => ("eq", None),
+            Operator::NotEqual => ("ne", Some(("eq", false, true))),
+            Operator::Less => ("lt", None),
+            Operator::LessEqual => ("le", Some(("lt", true, true))),
+            Operator::Greater => ("gt", Some(("lt", true, false))),
+            Operator::GreaterEqual => ("ge", Some(("lt", false, true))),
+            _ => return None
+        };
+        let (method, swapped, negated) = match (class.functions.iter().find(|x| x.lock().unwrap().name == name), fallback) {
+            (Some(method), _) => (method.clone(), false, false),
+            (None, Some((base, swapped, negated))) if class.functions.iter().any(|x| x.lock().unwrap().name == base) => {
+                if swapped && lhs.1 != rhs.1 {
+                    panic!("Cannot derive '{}' of '{}' from '{}' at {}, the operands have different types!", name, class.name, base, position.start);
+                }
+                (class.functions.iter().find(|x| x.lock().unwrap().name == base).unwrap().clone(), swapped, negated)
+            },
+            // Instances are still compared by identity
+            _ if matches!(op.data, Operator::Equal | Operator::NotEqual) => return None,
+            _ => panic!("Class '{}' has no operator method '{}' at {}!", class.name, name, position.start)
+        };
+
+        let method = method.lock().unwrap().clone();
+        if !matches!(method.function_type, FunctionType::Function) {
+            panic!("'{}' of '{}' must be a method to be used as an operator at {}!", method.name, class.name, position.start);
+        }
+        if !method.public && !self.scope.in_class(&class.name) {
+            panic!("'{}' is private to class '{}' at {}!", method.name, class.name, position.start);
+        }
+        let (instance, (value, found)) = if swapped { (rhs.0, lhs) } else { (lhs.0, rhs) };
+        let [expected] = method.params.as_slice() else {
+            panic!("Operator method '{}' of '{}' must take a single parameter at {}!", method.name, class.name, position.start);
+        };
+        if !self.check_data_type(expected.clone(), found.clone()) {
+            panic!("Cannot use '{}' with '{}' of '{}' (expected '{}') at {}!", found, method.name, class.name, expected, position.start);
+        }
+        let comparison = !matches!(op.data, Operator::Plus | Operator::Minus | Operator::Multiply | Operator::Divide);
+        if comparison && !method.data_type.is_named("bool") {
+            panic!("Comparison method '{}' of '{}' must return 'bool' at {}!", method.name, class.name, position.start);
+        }
+        let value = self.coerce(value, Some(found), expected);
+
+        // New instances given to the operator are freed with the scope (a + b + c frees a + b)
+        let instance_type = DataType::Custom(class.name.clone());
+        let instance = self.bind_temporary(instance, &instance_type);
+        let value = self.bind_temporary(value, expected);
+
+        let call = position.convert(Node::FunctionCall { 
+            name: position.convert(method.c_name()), 
+            params: vec![FunctionCallParameter { value: instance }, FunctionCallParameter { value }] 
+        });
+        if negated {
+            return Some((NodeInfo::new(Some(method.data_type), None), vec![
+                position.convert(Node::UnaryOperation { op: op.convert(UnaryOperator::Not), value: Box::new(call) })
+            ]));
+        }
+        Some((NodeInfo::new(Some(method.data_type), None), vec![call]))
+    }
+
+    fn check_unary_operation(&mut self, position: Positioned<()>, op: Positioned<UnaryOperator>, value: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (value_info, value_ast) = self.check_node(value.clone());
+
+        let Some(data_type) = value_info.data_type else {
+            panic!("Could not infer type of '{}' at {}!", value.data, value.start);
+        };
+
+        let (data_type, symbol) = match op.data {
+            UnaryOperator::AddressOf => {
+                // Only values stored somewhere have an address
+                let is_place = match &value.data {
+                    Node::Value(ValueNode::VariableCall(_) | ValueNode::This) | Node::Index { .. } => true,
+                    Node::BinaryOperation { op, .. } => matches!(op.data, Operator::MemberAccess),
+                    Node::UnaryOperation { op, .. } => matches!(op.data, UnaryOperator::Dereference),
+                    _ => false
+                };
+                if !is_place {
+                    panic!("Cannot take the address of '{}' at {}!", value.data, value.start);
+                }
+
+                let constant = match value_info.symbol {
+                    Some(Symbol::Variable(variable)) => {
+                        let mut variable = variable.lock().unwrap();
+                        // The pointer can be used to initialize the variable
+                        variable.initialized = true;
+                        variable.var_type == VarType::Constant
+                    },
+                    Some(_) => panic!("Cannot take the address of '{}' at {}!", value.data, value.start),
+                    None => false
+                };
+                (DataType::Pointer(constant, Box::new(data_type.normalized())), None)
+            },
+            UnaryOperator::Dereference => {
+                let DataType::Pointer(constant, inner) = data_type else {
+                    panic!("Cannot dereference '{}' of type '{}' at {}!", value.data, data_type, value.start);
+                };
+                if inner.is_named("c_void") || self.is_opaque(&inner) {
+                    panic!("Cannot dereference '{}' of type '{}' at {}!", value.data, DataType::Pointer(constant, inner), value.start);
+                }
+
+                // The pointed value behaves like a variable (constant behind a const pointer)
+                let var_type = if constant { VarType::Constant } else { VarType::Variable };
+                let variable = VariableSymbol::new(var_type, value.data.to_string(), Some(*inner.clone()), true);
+                (*inner, Some(Symbol::Variable(Arc::new(Mutex::new(variable)))))
+            },
+            UnaryOperator::Not => {
+                if !data_type.is_named("bool") {
+                    panic!("Expected 'bool' but found '{}' of type '{}' at {}!", value.data, data_type, value.start);
+                }
+                (data_type, None)
+            }
+        };
+
+        (NodeInfo::new(Some(data_type), symbol), vec![
+            position.convert(Node::UnaryOperation { op, value: Box::new(value_ast[0].clone()) })
+        ])
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn check_variable_definition(&mut self, position: Positioned<()>, var_type: Positioned<VarType>, name: Positioned<String>, data_type: Option<Positioned<TypeExpr>>, value: Option<Box<Positioned<Node>>>, public: bool, attributes: Vec<Attribute>) -> (NodeInfo, Vec<Positioned<Node>>) {
         if self.scope.get_variable(name.data.clone()).is_some() {
             panic!("Shadowing of variable impossible!");
         }
+        if public && matches!(self.scope.scope, ScopeType::Function(_, _) | ScopeType::Lambda | ScopeType::Block) {
+            panic!("Local variable '{}' cannot be public at {}!", name.data, position.start);
+        }
+        let global = matches!(self.scope.scope, ScopeType::Root);
+        match self.scope.scope {
+            ScopeType::Root => self.check_attributes(&attributes, &["export", "deprecated"], "globals", public),
+            ScopeType::Class(_) if self.scope.is_static() => self.check_attributes(&attributes, &["export", "deprecated"], "static fields", public),
+            ScopeType::Class(_) => self.check_attributes(&attributes, &[], "fields", public),
+            _ => self.check_attributes(&attributes, &[], "local variables", public)
+        }
+        if global && public {
+            self.check_linked_name(&name);
+        }
+        // The environment of a lambda only lives as long as the block creating it
+        if let (Some(Positioned { data: TypeExpr::Closure { .. }, .. }), ScopeType::Root | ScopeType::Class(_)) = (&data_type, &self.scope.scope) {
+            panic!("'{}' cannot hold a capturing lambda at {}, only local variables and parameters can!", name.data, name.start);
+        }
+        if global && data_type.is_none() && value.is_none() {
+            panic!("Global '{}' needs a type or a value at {}!", name.data, name.start);
+        }
 
         // Infer and check
-        let final_data_type = if let Some(value) = value.clone() {
-            let (value_info, _value_ast) = self.check_node(*value);
+        let (final_data_type, value) = if let Some(value) = value {
+            let (value_info, value_ast) = self.check_node(*value);
+            let found = value_info.data_type.clone();
 
-            if let Some(data_type) = data_type {
+            let final_data_type = if let Some(data_type) = &data_type {
+                let data_type = self.resolve_type(data_type);
                 if let Some(value_info_type) = value_info.data_type {
                     // Check if the types match
-                    if self.check_data_type(value_info_type, DataType::Custom(data_type.data.clone())) {
-                        Some(DataType::Custom(data_type.data.clone()))
+                    if self.check_data_type(data_type.clone(), value_info_type.clone()) {
+                        data_type
                     } else {
-                        panic!("Unexpected data_type!")
+                        self.initializer_mismatch(&name, &value_ast[0], &data_type, &value_info_type)
                     }
                 } else {
                     // Type can be inferred for rhs
-                    Some(self.infer_and_check(value_info, DataType::Custom(data_type.data.clone())))
+                    self.infer_and_check(value_info, data_type)
                 }
+            } else if let Some(DataType::Null) = value_info.data_type {
+                panic!("Cannot infer the type of '{}' from null, add a pointer type!", name.data)
+            } else if let Some(DataType::None) = value_info.data_type {
+                panic!("Cannot infer the type of '{}' from none, add an optional type!", name.data)
+            } else if let Some(value_info_type) = value_info.data_type {
+                value_info_type.clone()
             } else {
-                if let Some(value_info_type) = value_info.data_type {
-                    Some(value_info_type.clone())
-                } else {
-                    panic!("Cannot infer type (type constraints not available)")
-                }
+                panic!("Cannot infer type (type constraints not available)")
+            };
+
+            // TODO: check if more than 1 value (in the ast)
+            let value = self.coerce(value_ast[0].clone(), found, &final_data_type);
+            let value = self.fold(value, &final_data_type);
+            if global && !Self::is_constant(&value.data) {
+                panic!("Global '{}' needs a compile-time initializer at {}!", name.data, value.start);
             }
-        } else if let Some(data_type) = data_type {
-            Some(DataType::Custom(data_type.data.clone()))
+            (Some(final_data_type), Some(Box::new(value)))
+        } else if global && var_type.data == VarType::Constant {
+            panic!("Constant '{}' must be initialized at {}!", name.data, name.start);
+        } else if let Some(data_type) = &data_type {
+            (Some(self.resolve_type(data_type)), None)
         } else {
-            None
+            (None, None)
         };
 
         // Add Symbol
-        self.scope.variables.push(Arc::new(Mutex::new(VariableSymbol::new(var_type.data.clone(), name.data.clone(), final_data_type.clone(), value.is_some()))));
+        let mut symbol = VariableSymbol::new(var_type.data.clone(), name.data.clone(), final_data_type.clone(), value.is_some() || global);
+        symbol.public = public;
+        if let (VarType::Constant, Some(Node::Value(ValueNode::Decimal(folded))), Some(data_type)) = (&var_type.data, value.as_ref().map(|value| &value.data), &final_data_type) {
+            symbol.value = Self::integer_range(data_type).and(folded.parse().ok());
+        }
+        if let (Some(value), Some(_), ScopeType::Function(_, _) | ScopeType::Block) = (&value, self.is_class(&final_data_type), &self.scope.scope) {
+            symbol.owned = self.is_owning_value(value);
+        }
+        self.scope.variables.push(Arc::new(Mutex::new(symbol)));
+
+        // Keep the written type (and its position) when there is one
+        let data_type = match data_type {
+            Some(data_type) => Some(self.fold_type(&data_type)),
+            None => final_data_type.map(|x| self.type_expr(&position, &x))
+        };
 
         // Push AST
         (NodeInfo::new(Some(DataType::Void), None), vec![
             position.convert(Node::VariableDefinition { 
                 var_type: var_type.clone(), 
                 name: name.clone(), 
-                data_type: final_data_type.map(|x| position.convert(x.to_string())), // TODO: find a better option than using position (maybe value) 
-                value: value.clone() 
+                data_type,
+                value: value.clone(),
+                public,
+                attributes
             })
         ])
     }
 
-    fn check_function_definition(&mut self, position: Positioned<()>, name: Positioned<String>, return_type: Option<Positioned<String>>, params: Vec<FunctionDefinitionParameter>, body: Option<Vec<Positioned<Node>>>, constructor: bool) -> (NodeInfo, Vec<Positioned<Node>>) {
+    #[allow(clippy::too_many_arguments)]
+    fn check_function_definition(&mut self, position: Positioned<()>, name: Positioned<String>, return_type: Option<Positioned<TypeExpr>>, params: Vec<FunctionDefinitionParameter>, body: Option<Vec<Positioned<Node>>>, constructor: bool, constant: bool, variadic: bool, public: bool, attributes: Vec<Attribute>) -> (NodeInfo, Vec<Positioned<Node>>) {
         if self.scope.get_function(name.data.clone()).is_some() {
             panic!("Shadowing of function impossible!");
         }
 
         match self.scope.scope {
-            ScopeType::Function(_, _) => panic!("Cannot declare function inside of function!"),
+            ScopeType::Function(_, _) | ScopeType::Block => panic!("Cannot declare function inside of function!"),
             _ => {}
         }
 
-        let data_type = return_type.map_or_else(|| DataType::Void, |x| DataType::Custom(x.data.clone()));
+        let owner = match &self.scope.scope {
+            ScopeType::Class(class) => Some(class.clone()),
+            _ => None
+        };
+        let data_type = match (&owner, constructor) {
+            (Some(owner), true) => DataType::Custom(owner.clone()),
+            _ => return_type.as_ref().map_or(DataType::Void, |x| self.resolve_type(x))
+        };
+        if let DataType::Array(_, _) = data_type {
+            panic!("Function '{}' cannot return a fixed-size array at {}, its memory is on the stack!", name.data, name.start);
+        }
+        if let DataType::Closure(_, _) = data_type {
+            panic!("Function '{}' cannot return a capturing lambda at {}, its environment does not outlive the function!", name.data, name.start);
+        }
         let function_type = if body.is_none() {
             FunctionType::ExternalFunction
         } else if constructor {
             FunctionType::Constructor
+        } else if owner.is_some() && name.data == "drop" {
+            FunctionType::Destructor
         } else {
             FunctionType::Function
         };
+        if public && owner.is_none() && !matches!(function_type, FunctionType::ExternalFunction) {
+            self.check_linked_name(&name);
+        }
+        match function_type {
+            FunctionType::ExternalFunction => self.check_attributes(&attributes, &[], "extern functions", public),
+            FunctionType::Destructor => self.check_attributes(&attributes, &[], "destructors", public),
+            _ => self.check_attributes(&attributes, &["inline", "export", "deprecated", "cold"], "functions", public)
+        }
 
         // Add Symbol
-        self.scope.functions.push(Arc::new(Mutex::new(FunctionSymbol::new(name.data.clone(), data_type.clone(), function_type, params.clone()))));
+        let param_types: Vec<DataType> = params.iter().map(|param| self.resolve_type(&param.data_type)).collect();
+        if constant {
+            if matches!(function_type, FunctionType::Destructor) {
+                panic!("Destructors cannot be const at {}!", name.start);
+            }
+            if Self::integer_range(&data_type).is_none() || param_types.iter().any(|param| Self::integer_range(param).is_none()) {
+                panic!("Const function '{}' can only take and return integers at {}!", name.data, name.start);
+            }
+        }
+        let returns_instance = self.is_returned_class(&data_type);
+        let mut symbol = FunctionSymbol::new(name.data.clone(), data_type.clone(), function_type.clone(), param_types, public, owner);
+        symbol.variadic = variadic;
+        symbol.fresh = matches!(function_type, FunctionType::Constructor);
+        let symbol = Arc::new(Mutex::new(symbol));
+        self.scope.functions.push(symbol.clone());
+        let params: Vec<FunctionDefinitionParameter> = params.into_iter().map(|param| FunctionDefinitionParameter { 
+            data_type: self.fold_type(&param.data_type), 
+            name: param.name 
+        }).collect();
+        if constant {
+            // Replaced once checked, allows recursive calls
+            symbol.lock().unwrap().body = Some((Vec::new(), Vec::new()));
+        }
         
         
         // Process body
@@ -564,26 +2006,55 @@ impl Checker {
             
             // TODO: Push the params in the scope
             for param in params.iter() {
+                let data_type = self.resolve_type(&param.data_type);
                 self.scope.variables.push(Arc::new(Mutex::new(VariableSymbol { 
                     var_type: VarType::Constant, 
                     name: param.name.data.clone(), 
-                    data_type: Some(DataType::Custom(param.data_type.data.clone())), 
-                    initialized: true 
+                    data_type: Some(data_type), 
+                    initialized: true,
+                    public: true,
+                    owned: false,
+                    moved: false,
+                    value: None
                 })))
             }
 
             // TODO: Add allocation if constructor
 
             // Check body
+            let fresh = std::mem::replace(&mut self.fresh, true);
             let mut new_body = Vec::new();
             for node in body {
                 let (node_info, mut node_ast) = self.check_node(node.clone());
 
                 // TODO: also check last node's type
 
+                new_body.append(&mut self.hoisted);
                 new_body.append(&mut node_ast);
             }
 
+            // Run the deferred expressions and free the class instances owned by the function
+            if !matches!(new_body.last().map(|x| &x.data), Some(Node::Return(_))) {
+                let mut frees = self.cleanup(&position, false, None);
+                new_body.append(&mut frees);
+            }
+
+            // Recursive calls did not take ownership (the result was not known yet)
+            if returns_instance && matches!(function_type, FunctionType::Function | FunctionType::Static) {
+                symbol.lock().unwrap().fresh = self.fresh;
+            }
+            self.fresh = fresh;
+
+            // The evaluator runs the body of const functions
+            if constant {
+                for node in new_body.iter() {
+                    if !self.is_constant_statement(node) {
+                        panic!("Const function '{}' can only define constants, branch and return constant expressions, found '{}' at {}!", name.data, node.data, node.start);
+                    }
+                }
+                symbol.lock().unwrap().body = Some((params.iter().map(|param| param.name.data.clone()).collect(), new_body.clone()));
+            }
+
             // Exit scope
             let scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
             self.scope = *scope.parent.unwrap();
@@ -593,111 +2064,1446 @@ impl Checker {
         };
 
         (NodeInfo::new(Some(DataType::Void), None), vec![
-            position.convert(Node::FunctionDefinition { name, return_type: Some(position.convert(data_type.to_string())), params, body: new_body, constructor }) // TODO: change the position of data_type
+            position.convert(Node::FunctionDefinition { name, return_type: Some(return_type.unwrap_or_else(|| self.type_expr(&position, &data_type))), params, body: new_body, constructor, constant, variadic, public, attributes })
         ])
     }
 
     fn check_return(&mut self, position: Positioned<()>, value: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
-        match self.scope.scope.clone() {
-            ScopeType::Function(_, data_type) => {
+        if let Node::Interpolation(_) = value.data {
+            panic!("Cannot return an interpolated string at {}, its buffer is on the stack!", position.start);
+        }
+        match self.scope.return_type() {
+            Some(data_type) => {
                 let (value_info, value_ast) = self.check_node(value).clone();
+                let found = value_info.data_type.clone();
+                if let Some(DataType::Closure(_, _)) = found {
+                    panic!("Cannot return a capturing lambda at {}, its environment does not outlive the function!", position.start);
+                }
                 let data_type = self.infer_and_check(value_info, data_type);
 
                 // TODO: check if more than 1 value (in the ast)
-                (NodeInfo::new(Some(DataType::Void), None), vec![position.convert(Node::Return(Box::new(value_ast[0].clone())))])
+                let value = self.coerce(value_ast[0].clone(), found, &data_type);
+                if let Some(slice) = self.stack_slice(&value) {
+                    panic!("Cannot return '{}' at {}, it views memory on the stack of the function!", slice.data, slice.start);
+                }
+
+                // The returned instance is given to the caller (ok(instance) too)
+                let instance = match &value.data {
+                    Node::Result { ok: true, value: Some(instance), .. } => instance.as_ref().clone(),
+                    _ => value.clone()
+                };
+                let returned = self.owned_variable(&instance);
+                let fresh = returned.is_some() || matches!(instance.data, Node::Value(ValueNode::Null | ValueNode::None)) || self.is_fresh(&instance);
+                if !fresh && self.is_returned_class(&data_type) {
+                    self.fresh = false;
+                }
+                let mut ast = self.cleanup(&position, true, returned.as_ref());
+                if ast.is_empty() || returned.is_some() {
+                    ast.push(position.convert(Node::Return(Box::new(value))));
+                } else {
+                    // The value is computed before the cleanup
+                    let result = position.convert("apla_result".to_string());
+                    ast.insert(0, position.convert(Node::VariableDefinition { 
+                        var_type: position.convert(VarType::Constant), 
+                        name: result.clone(), 
+                        data_type: Some(self.type_expr(&position, &data_type)), 
+                        value: Some(Box::new(value)), 
+                        public: false,
+                        attributes: Vec::new()
+                    }));
+                    ast.push(position.convert(Node::Return(Box::new(result.convert(Node::Value(ValueNode::VariableCall(result.data.clone())))))));
+                }
+                (NodeInfo::new(Some(DataType::Void), None), ast)
             },
-            _ => panic!("Unexpected return statement!")
+            None => panic!("Unexpected return statement!")
         }
     }
 
-    fn check_function_call(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<FunctionCallParameter>) -> (NodeInfo, Vec<Positioned<Node>>) {
-        if let Some(function_arc) = self.scope.get_function(name.data.clone()) {
-            let function = function_arc.lock().unwrap();
-            let mut new_params = Vec::new();
-            let mut index = 0;
-            for param in function.params.iter() {
-                if let Some(given_param) = params.get(index) {
-                    let (param_info, param_ast) = self.check_node(given_param.value.clone());
-
-                    let data_type = self.infer_and_check(param_info, DataType::Custom(param.data_type.data.clone()));
-
-                    // TODO: check if more than 1 value (in the ast)
-                    new_params.push(FunctionCallParameter { value: param_ast[0].clone() });
-                } else {
-                    panic!("Not enough params");
-                }
-                index += 1;
-            }
+    fn check_call_params(&mut self, expected: Vec<DataType>, variadic: bool, mut params: Vec<FunctionCallParameter>) -> Vec<FunctionCallParameter> {
+        if params.len() < expected.len() {
+            panic!("Not enough params");
+        }
+        if params.len() > expected.len() && !variadic {
+            panic!("Too many params");
+        }
 
-            if params.len() > index {
-                panic!("Too many params");
+        let trailing = params.split_off(expected.len());
+        let mut new_params = Vec::new();
+        for (expected, param) in expected.into_iter().zip(params) {
+            let (param_info, param_ast) = self.check_node(param.value);
+            let found = param_info.data_type.clone();
+            let data_type = self.infer_and_check(param_info, expected);
+
+            // TODO: check if more than 1 value (in the ast)
+            let value = self.coerce(param_ast[0].clone(), found, &data_type);
+            new_params.push(FunctionCallParameter { value });
+        }
+
+        // Variadic arguments go through the default promotions of C (char / short => int, float => double)
+        for param in trailing {
+            let (param_info, param_ast) = self.check_node(param.value.clone());
+            match param_info.data_type {
+                Some(DataType::Null) => panic!("Cannot pass null as a variadic argument at {}, cast it to a pointer type!", param.value.start),
+                Some(DataType::Array(_, _)) => panic!("Cannot pass the array '{}' as a variadic argument at {}, pass a pointer to its first element!", param.value.data, param.value.start),
+                Some(data_type @ (DataType::Void | DataType::None | DataType::Slice(_) | DataType::Closure(_, _) | DataType::Generic(_, _) | DataType::ResultValue(_, _))) => 
+                    panic!("Cannot pass '{}' of type '{}' as a variadic argument at {}!", param.value.data, data_type, param.value.start),
+                Some(_) => {},
+                None => panic!("Could not infer the type of '{}' at {}!", param.value.data, param.value.start)
             }
+            new_params.push(FunctionCallParameter { value: param_ast[0].clone() });
+        }
+        new_params
+    }
 
-            (NodeInfo::new(Some(function.data_type.clone()), None), vec![
-                position.convert(Node::FunctionCall { name: name.clone(), params: new_params })
-            ])
-        } else {
-            panic!("Function '{}' not found!", name.data);
+    fn is_class(&mut self, data_type: &Option<DataType>) -> Option<String> {
+        match data_type {
+            Some(DataType::Custom(name)) if self.find_class(name.clone()).is_some_and(|class| class.lock().unwrap().kind == ClassKind::Class) => Some(name.clone()),
+            Some(DataType::Optional(inner)) => self.is_class(&Some(*inner.clone())),
+            _ => None
         }
     }
 
-    fn check_include(&mut self, position: Positioned<()>, path: Positioned<String>) -> (NodeInfo, Vec<Positioned<Node>>) {
-        // TODO: find a way to check if the path is valid and exists.
-        self.includes.push((position, path));
-        (NodeInfo::new(Some(DataType::Void), None), vec![
-            // No need to add the node (post processed)
-        ])
+    // Stores a new instance in a hidden variable owning it
+    fn bind_temporary(&mut self, value: Positioned<Node>, data_type: &DataType) -> Positioned<Node> {
+        if !matches!(self.scope.scope, ScopeType::Function(_, _) | ScopeType::Block) || self.is_class(&Some(data_type.clone())).is_none() || !self.is_fresh(&value) {
+            return value;
+        }
+        let name = format!("apla_temporary_{}", self.tries);
+        self.tries += 1;
+        let mut symbol = VariableSymbol::new(VarType::Constant, name.clone(), Some(data_type.clone()), true);
+        symbol.owned = true;
+        self.scope.variables.push(Arc::new(Mutex::new(symbol)));
+        self.hoisted.push(value.convert(Node::VariableDefinition { 
+            var_type: value.convert(VarType::Constant), 
+            name: value.convert(name.clone()), 
+            data_type: Some(self.type_expr(&value.convert(()), data_type)), 
+            value: Some(Box::new(value.clone())), 
+            public: false,
+            attributes: Vec::new()
+        }));
+        value.convert(Node::Value(ValueNode::VariableCall(name)))
     }
 
-    fn check_class_definition(&mut self, position: Positioned<()>, name: Positioned<String>, body: Vec<Positioned<Node>>) -> (NodeInfo, Vec<Positioned<Node>>) {
-        // check if class doesn't exists
-        if self.scope.symbol_exists(name.data.clone()) {
-            panic!("Symbol already exists");
+    fn owned_variable(&mut self, value: &Positioned<Node>) -> Option<Arc<Mutex<VariableSymbol>>> {
+        let Node::Value(ValueNode::VariableCall(name)) = &value.data else {
+            return None;
+        };
+        self.scope.get_variable(name.clone()).filter(|variable| variable.lock().unwrap().owned)
+    }
+
+    // Owned variables used as a value give their instance away
+    fn moved_variable(&mut self, value: &Positioned<Node>) -> Option<Arc<Mutex<VariableSymbol>>> {
+        let variable = self.owned_variable(value)?;
+        // The instance would still be freed (or leak) on the other paths
+        if !self.scope.variables.iter().any(|x| Arc::ptr_eq(x, &variable)) {
+            panic!("Cannot move '{}' out of an enclosing scope at {}!", value.data, value.start);
         }
-        
-        // Add Symbol
-        let class = Arc::new(Mutex::new(ClassSymbol::new(name.data.clone())));
-        self.scope.classes.push(class.clone());
+        let mut symbol = variable.lock().unwrap();
+        symbol.owned = false;
+        symbol.moved = true;
+        drop(symbol);
+        Some(variable)
+    }
 
-        // Enter scope
-        let mut scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
-        self.scope = Scope::new(ScopeType::Class(name.data.clone()), Some(Box::new(scope)));
-        
-        // Add Self
-        self.scope.variables.push(Arc::new(Mutex::new(VariableSymbol::new(VarType::Constant, "self".to_string(), Some(DataType::Custom(name.data.clone())), true))));
+    // Instances created by constructors and returned by functions are owned by the caller
+    fn is_owning_value(&mut self, value: &Positioned<Node>) -> bool {
+        self.is_fresh(value) || self.moved_variable(value).is_some()
+    }
 
-        // Check the body
-        let mut new_body = Vec::new();
-        for node in body {
-            match &node.data {
-                Node::VariableDefinition { var_type, name, data_type, value } => {
-                    let (_, mut ast) = self.check_node(node);
-                    new_body.append(&mut ast);
-                    // Add variable to symbol (last symbol)
-                    let field_symbol = self.scope.variables.last().clone().cloned().unwrap();
-                    class.lock().unwrap().fields.push(field_symbol);
-                    // self.scope.parent.as_mut().unwrap().classes.last_mut().unwrap().lock().unwrap().fields.push(field_symbol);
-                    // TODO: process default value (to be in constructor) [not supported for now]
-                },
-                Node::FunctionDefinition { name, return_type, params, body, constructor } => {
-                    let (_, mut ast) = self.check_node(node);
-                    new_body.append(&mut ast);
-                    // Add function to symbol (last symbol)
-                    let function_symbol = self.scope.functions.last().clone().cloned().unwrap();
-                    class.lock().unwrap().functions.push(function_symbol);
-                    // self.scope.parent.as_mut().unwrap().classes.last_mut().unwrap().lock().unwrap().functions.push(function_symbol);
-                }
-                _ => panic!("Unexpected node")
+    fn is_fresh(&mut self, value: &Positioned<Node>) -> bool {
+        match &value.data {
+            Node::FunctionCall { name, .. } => self.find_c_function(&name.data).is_some_and(|function| function.lock().unwrap().fresh),
+            // Values unwrapped by "?" come from the call stored in the temporary
+            Node::BinaryOperation { lhs, op, .. } if matches!(op.data, Operator::FieldAccess) => {
+                let Node::Value(ValueNode::VariableCall(name)) = &lhs.data else {
+                    return false;
+                };
+                let stored = self.hoisted.iter().find_map(|node| match &node.data {
+                    Node::VariableDefinition { name: temporary, value: Some(stored), .. } if temporary.data == *name && name.starts_with("apla_try_") => Some(stored.as_ref().clone()),
+                    _ => None
+                });
+                stored.is_some_and(|stored| self.is_fresh(&stored))
+            },
+            _ => false
+        }
+    }
+
+    // Functions and methods of any module by their C name
+    fn find_c_function(&mut self, c_name: &str) -> Option<Arc<Mutex<FunctionSymbol>>> {
+        if let Some(function) = self.scope.get_c_function(c_name) {
+            return Some(function);
+        }
+        for module in self.modules.iter() {
+            let module = module.lock().unwrap();
+            let classes = module.classes.iter().flat_map(|class| class.lock().unwrap().functions.clone());
+            if let Some(function) = module.functions.iter().cloned().chain(classes).find(|function| function.lock().unwrap().c_name() == c_name) {
+                return Some(function);
             }
         }
+        None
+    }
 
-        // Exit the scope
-        scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
-        self.scope = *scope.parent.unwrap();
+    // Class instances (or Results of them) returned by a function
+    fn is_returned_class(&mut self, data_type: &DataType) -> bool {
+        match data_type {
+            DataType::Generic(name, params) if name == "Result" => self.is_class(&Some(params[0].clone())).is_some(),
+            data_type => self.is_class(&Some(data_type.clone())).is_some()
+        }
+    }
 
-        (NodeInfo::new(Some(DataType::Void), None), vec![
-            position.convert(Node::ClassDefinition { name: name.clone(), body: new_body })
-        ])
+    // Slices of array literals and local arrays (in the returned value) do not outlive the function
+    fn stack_slice(&mut self, value: &Positioned<Node>) -> Option<Positioned<Node>> {
+        match &value.data {
+            Node::Slice { value: array, .. } => {
+                // The array is stored in a local when indexing or accessing fields of it
+                let mut root = array.as_ref();
+                while let Node::Index { value, .. } | Node::BinaryOperation { lhs: value, op: Positioned { data: Operator::FieldAccess, .. }, .. } = &root.data {
+                    root = value;
+                }
+                let local = match &root.data {
+                    Node::Array { .. } => true,
+                    Node::Value(ValueNode::VariableCall(name)) => self.scope.get_variable(name.clone()).is_some_and(|variable| self.scope.is_local(&variable)),
+                    _ => false
+                };
+                local.then(|| array.as_ref().clone())
+            },
+            Node::Result { value: Some(value), .. } => self.stack_slice(value),
+            Node::Tuple { values, .. } => values.iter().find_map(|value| self.stack_slice(value)),
+            _ => None
+        }
+    }
+
+    // Initializers of globals must be C constant expressions (literals and arithmetic on them)
+    fn is_constant(node: &Node) -> bool {
+        match node {
+            Node::Value(value) => matches!(value, ValueNode::Decimal(_) | ValueNode::Float(_) | ValueNode::String(_) | ValueNode::Bool(_) | ValueNode::Null | ValueNode::None),
+            Node::BinaryOperation { lhs, op, rhs } => !matches!(op.data, Operator::MemberAccess | Operator::FieldAccess | Operator::Assignment) &&
+                Self::is_constant(&lhs.data) && Self::is_constant(&rhs.data),
+            Node::Cast { value, .. } |
+            Node::UnaryOperation { op: Positioned { data: UnaryOperator::Not, .. }, value } => Self::is_constant(&value.data),
+            Node::Array { values, .. } |
+            Node::Tuple { values, .. } => values.iter().all(|value| Self::is_constant(&value.data)),
+            _ => false
+        }
+    }
+
+    // Integer types (the only values of the constant evaluator) and their range
+    fn integer_range(data_type: &DataType) -> Option<(i128, i128)> {
+        match data_type {
+            DataType::CDecimal => Some((i32::MIN.into(), i32::MAX.into())),
+            DataType::Custom(name) => match name.as_str() {
+                "c_char" => Some((i8::MIN.into(), i8::MAX.into())),
+                "c_short" => Some((i16::MIN.into(), i16::MAX.into())),
+                "c_int" => Some((i32::MIN.into(), i32::MAX.into())),
+                "c_long" => Some((i64::MIN.into(), i64::MAX.into())),
+                _ => None
+            },
+            DataType::Number(number) => number.range(),
+            _ => None
+        }
+    }
+
+    // Value of a checked expression at compile time, None if it depends on the runtime
+    // (locals are the parameters and constants of the const function being evaluated)
+    fn evaluate(&mut self, node: &Positioned<Node>, data_type: Option<&DataType>, locals: &[(String, i128)], depth: usize) -> Option<i128> {
+        let overflow = || -> i128 {
+            panic!("Overflow in constant expression '{}' at {}!", node.data, node.start);
+        };
+        let value = match &node.data {
+            Node::Value(ValueNode::Decimal(value)) => value.parse().ok()?,
+            Node::Value(ValueNode::Bool(value)) => (*value).into(),
+            Node::Value(ValueNode::VariableCall(name)) => match locals.iter().rev().find(|(local, _)| local == name) {
+                Some((_, value)) => *value,
+                None => self.scope.get_variable(name.clone())?.lock().unwrap().value?
+            },
+            Node::BinaryOperation { lhs, op, rhs } => {
+                // Compared values have their own type
+                let arithmetic = matches!(op.data, Operator::Plus | Operator::Minus | Operator::Multiply | Operator::Divide);
+                let operand_type = if arithmetic { data_type } else { None };
+                let lhs = self.evaluate(lhs, operand_type, locals, depth)?;
+                // The right side of a short circuit is never reached
+                match op.data {
+                    Operator::And if lhs == 0 => return Some(0),
+                    Operator::Or if lhs != 0 => return Some(1),
+                    _ => {}
+                }
+                let rhs = self.evaluate(rhs, operand_type, locals, depth)?;
+                match op.data {
+                    Operator::Plus => lhs.checked_add(rhs).unwrap_or_else(overflow),
+                    Operator::Minus => lhs.checked_sub(rhs).unwrap_or_else(overflow),
+                    Operator::Multiply => lhs.checked_mul(rhs).unwrap_or_else(overflow),
+                    Operator::Divide if rhs == 0 => panic!("Division by zero in constant expression '{}' at {}!", node.data, node.start),
+                    Operator::Divide => lhs / rhs,
+                    Operator::Equal => (lhs == rhs).into(),
+                    Operator::NotEqual => (lhs != rhs).into(),
+                    Operator::Less => (lhs < rhs).into(),
+                    Operator::LessEqual => (lhs <= rhs).into(),
+                    Operator::Greater => (lhs > rhs).into(),
+                    Operator::GreaterEqual => (lhs >= rhs).into(),
+                    Operator::And |
+                    Operator::Or => (rhs != 0).into(),
+                    _ => return None
+                }
+            },
+            Node::UnaryOperation { op: Positioned { data: UnaryOperator::Not, .. }, value } => (self.evaluate(value, None, locals, depth)? == 0).into(),
+            Node::Cast { value, data_type: target } => {
+                let (min, max) = Self::integer_range(&self.resolve_type(target))?;
+                let value = self.evaluate(value, None, locals, depth)?;
+                // Wraps around like C
+                (value - min).rem_euclid(max - min + 1) + min
+            },
+            Node::FunctionCall { name, params } => {
+                let function = self.scope.get_c_function(&name.data)?.lock().unwrap().clone();
+                let (names, body) = function.body?;
+                if depth >= 256 {
+                    panic!("Constant evaluation of '{}' is too deep at {}!", node.data, node.start);
+                }
+                let mut arguments = Vec::new();
+                for ((name, param), data_type) in names.into_iter().zip(params.iter()).zip(function.params.iter()) {
+                    arguments.push((name, self.evaluate(&param.value, Some(data_type), locals, depth)?));
+                }
+                self.evaluate_body(&body, &function.data_type, &mut arguments, depth + 1)?
+            },
+            _ => return None
+        };
+
+        if let Some((data_type, (min, max))) = data_type.and_then(|data_type| Self::integer_range(data_type).map(|range| (data_type, range))) {
+            if value < min || value > max {
+                panic!("Constant expression '{}' = {} overflows '{}' at {}!", node.data, value, data_type.normalized(), node.start);
+            }
+        }
+        Some(value)
+    }
+
+    // Runs the checked body of a const function until it returns
+    fn evaluate_body(&mut self, body: &[Positioned<Node>], return_type: &DataType, locals: &mut Vec<(String, i128)>, depth: usize) -> Option<i128> {
+        for node in body {
+            match &node.data {
+                Node::VariableDefinition { name, data_type, value: Some(value), .. } => {
+                    let data_type = data_type.as_ref().map(|data_type| self.resolve_type(data_type));
+                    let value = self.evaluate(value, data_type.as_ref(), locals, depth)?;
+                    locals.push((name.data.clone(), value));
+                },
+                Node::If { condition, body, else_body } => {
+                    let branch = if self.evaluate(condition, None, locals, depth)? != 0 {
+                        Some(body)
+                    } else {
+                        else_body.as_ref()
+                    };
+                    let Some(branch) = branch else {
+                        continue;
+                    };
+                    // Constants of the branch are not visible after it
+                    let length = locals.len();
+                    let value = self.evaluate_body(branch, return_type, locals, depth);
+                    locals.truncate(length);
+                    if value.is_some() {
+                        return value;
+                    }
+                },
+                Node::Return(value) => return self.evaluate(value, Some(return_type), locals, depth),
+                _ => return None
+            }
+        }
+        None
+    }
+
+    // Statements the evaluator can run (checked AST of const functions)
+    fn is_constant_statement(&mut self, node: &Positioned<Node>) -> bool {
+        match &node.data {
+            Node::VariableDefinition { var_type, value: Some(value), .. } => var_type.data == VarType::Constant && self.is_constant_expression(value),
+            Node::If { condition, body, else_body } => self.is_constant_expression(condition) && 
+                body.iter().chain(else_body.iter().flatten()).all(|node| self.is_constant_statement(node)),
+            Node::Return(value) => self.is_constant_expression(value),
+            _ => false
+        }
+    }
+
+    fn is_constant_expression(&mut self, node: &Positioned<Node>) -> bool {
+        match &node.data {
+            Node::Value(ValueNode::Decimal(_) | ValueNode::Bool(_)) => true,
+            // Parameters, local and global constants
+            Node::Value(ValueNode::VariableCall(name)) => self.scope.get_variable(name.clone())
+                .is_some_and(|variable| variable.lock().unwrap().var_type == VarType::Constant),
+            Node::BinaryOperation { lhs, op, rhs } => !matches!(op.data, Operator::MemberAccess | Operator::FieldAccess | Operator::Assignment) &&
+                self.is_constant_expression(lhs) && self.is_constant_expression(rhs),
+            Node::UnaryOperation { op, value } => matches!(op.data, UnaryOperator::Not) && self.is_constant_expression(value),
+            Node::Cast { value, data_type } => Self::integer_range(&self.resolve_type(data_type)).is_some() && self.is_constant_expression(value),
+            Node::FunctionCall { name, params } => self.scope.get_c_function(&name.data).is_some_and(|function| function.lock().unwrap().body.is_some()) && 
+                params.iter().all(|param| self.is_constant_expression(&param.value)),
+            _ => false
+        }
+    }
+
+    // Replaces integer and bool expressions known at compile time by their value
+    fn fold(&mut self, node: Positioned<Node>, data_type: &DataType) -> Positioned<Node> {
+        match (&node.data, data_type) {
+            (Node::Array { data_type: array_type, values }, DataType::Array(inner, _)) => {
+                let array_type = array_type.clone();
+                let values = values.clone().into_iter().map(|value| self.fold(value, inner)).collect();
+                node.convert(Node::Array { data_type: array_type, values })
+            },
+            (Node::Tuple { data_type: tuple_type, values }, DataType::Tuple(elements)) => {
+                let tuple_type = tuple_type.clone();
+                let values = values.clone().into_iter().zip(elements.iter()).map(|(value, element)| self.fold(value, element)).collect();
+                node.convert(Node::Tuple { data_type: tuple_type, values })
+            },
+            _ if Self::integer_range(data_type).is_some() => match self.evaluate(&node, Some(data_type), &[], 0) {
+                // C has no negative literals and the largest ones are unsigned
+                Some(value) if value == i64::MIN.into() => node.convert(Node::Value(ValueNode::Decimal(format!("({} - 1)", value + 1)))),
+                Some(value) if value > i64::MAX.into() => node.convert(Node::Value(ValueNode::Decimal(format!("{}U", value)))),
+                Some(value) => node.convert(Node::Value(ValueNode::Decimal(value.to_string()))),
+                None => node
+            },
+            _ if data_type.is_named("bool") => match self.evaluate(&node, None, &[], 0) {
+                Some(value) => node.convert(Node::Value(ValueNode::Bool(value != 0))),
+                None => node
+            },
+            _ => node
+        }
+    }
+
+    fn array_size(&mut self, size: &Positioned<String>) -> usize {
+        let constant = size.convert(Node::Value(ValueNode::VariableCall(size.data.clone())));
+        match self.evaluate(&constant, None, &[], 0) {
+            Some(value) if value > 0 => value as usize,
+            _ => panic!("Array size '{}' must be a positive constant at {}!", size.data, size.start)
+        }
+    }
+
+    // Array sizes given by constants are written as numbers for C
+    fn fold_type(&mut self, data_type: &Positioned<TypeExpr>) -> Positioned<TypeExpr> {
+        let mut folded = data_type.clone();
+        match &mut folded.data {
+            TypeExpr::Array { inner, size } => {
+                **inner = self.fold_type(inner);
+                if let Some(size) = size {
+                    if size.data.parse::<usize>().is_err() {
+                        size.data = self.array_size(size).to_string();
+                    }
+                }
+            },
+            TypeExpr::Pointer { inner, .. } | TypeExpr::Optional(inner) => **inner = self.fold_type(inner),
+            TypeExpr::Generic { params, .. } |
+            TypeExpr::Tuple(params) => *params = params.iter().map(|param| self.fold_type(param)).collect(),
+            _ => {}
+        }
+        folded
+    }
+
+    // Deferred expressions (last first) then destructor calls of the owned variables (last defined first),
+    // up to the function scope when returning
+    fn cleanup(&mut self, position: &Positioned<()>, returning: bool, except: Option<&Arc<Mutex<VariableSymbol>>>) -> Vec<Positioned<Node>> {
+        let mut ast = Vec::new();
+        let mut scope = &self.scope;
+        loop {
+            ast.extend(scope.deferred.iter().rev().cloned());
+            for variable in scope.variables.iter().rev() {
+                if except.is_some_and(|except| Arc::ptr_eq(except, variable)) {
+                    continue;
+                }
+                let variable = variable.lock().unwrap();
+                if !variable.owned {
+                    continue;
+                }
+                let class = match &variable.data_type {
+                    Some(DataType::Custom(class)) => class,
+                    Some(DataType::Optional(inner)) => match inner.as_ref() {
+                        DataType::Custom(class) => class,
+                        _ => continue
+                    },
+                    _ => continue
+                };
+                ast.push(position.convert(Node::FunctionCall { 
+                    name: position.convert(format!("{}_free", class)), 
+                    params: vec![FunctionCallParameter { value: position.convert(Node::Value(ValueNode::VariableCall(variable.name.clone()))) }] 
+                }));
+            }
+
+            match (&scope.scope, &scope.parent) {
+                (ScopeType::Function(_, _), _) => break,
+                (_, Some(parent)) if returning => scope = parent,
+                _ => break
+            }
+        }
+        ast
+    }
+
+    fn check_defer(&mut self, position: Positioned<()>, value: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if matches!(self.scope.scope, ScopeType::Root | ScopeType::Class(_) | ScopeType::Module(_)) {
+            panic!("Unexpected defer statement at {}!", position.start);
+        }
+        let (_, mut value_ast) = self.check_node(value);
+        if !self.hoisted.is_empty() {
+            panic!("Cannot use '?' or string interpolation in a deferred expression at {}!", position.start);
+        }
+        self.scope.deferred.append(&mut value_ast);
+        (NodeInfo::new(Some(DataType::Void), None), Vec::new())
+    }
+
+    fn check_if(&mut self, position: Positioned<()>, condition: Positioned<Node>, body: Vec<Positioned<Node>>, else_body: Option<Vec<Positioned<Node>>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if self.scope.return_type().is_none() {
+            panic!("Unexpected if statement at {}!", position.start);
+        }
+
+        let (condition, binding, narrowed, else_narrowed) = if let Node::VariableDefinition { name, data_type, value: Some(value), .. } = condition.data.clone() {
+            // "if let" binds the value of the optional in the body
+            let (value_info, value_ast) = self.check_node(*value);
+            let optional = match (&data_type, value_info.data_type) {
+                (None, Some(found @ DataType::Optional(_))) => found,
+                (Some(data_type), Some(found)) => {
+                    let data_type = DataType::Optional(Box::new(self.resolve_type(data_type)));
+                    if !self.check_data_type(data_type.clone(), found.clone()) {
+                        panic!("Cannot bind '{}' of type '{}' to '{}' at {}!", value_ast[0].data, found, data_type, position.start);
+                    }
+                    data_type
+                },
+                (_, found) => panic!("Cannot use 'if let' on '{}' of type '{}' at {}, it is not optional!", value_ast[0].data, found.map_or("unknown".to_string(), |x| x.to_string()), position.start)
+            };
+            let DataType::Optional(inner) = optional.clone() else {
+                unreachable!()
+            };
+
+            // The binding takes the instance like a variable definition
+            let mut binding = VariableSymbol::new(VarType::Constant, name.data.clone(), Some(*inner.clone()), true);
+            if self.is_class(&Some(*inner)).is_some() {
+                binding.owned = self.is_owning_value(&value_ast[0]);
+            }
+            let condition = condition.convert(Node::VariableDefinition { 
+                var_type: condition.convert(VarType::Constant), 
+                name, 
+                data_type: Some(self.type_expr(&position, &optional)), 
+                value: Some(Box::new(value_ast[0].clone())), 
+                public: false,
+                attributes: Vec::new()
+            });
+            (condition, Some(binding), None, None)
+        } else {
+            // "x != none" narrows x in the body, "x == none" in the else body
+            let narrowing = match &condition.data {
+                Node::BinaryOperation { lhs, op, rhs } => match (&lhs.data, &rhs.data) {
+                    (Node::Value(ValueNode::VariableCall(name)), Node::Value(ValueNode::None)) |
+                    (Node::Value(ValueNode::None), Node::Value(ValueNode::VariableCall(name))) => {
+                        self.scope.get_variable(name.clone())
+                            .filter(|variable| matches!(variable.lock().unwrap().data_type, Some(DataType::Optional(_))))
+                            .map(|variable| (variable, matches!(op.data, Operator::NotEqual)))
+                    },
+                    _ => None
+                },
+                _ => None
+            };
+
+            let (condition_info, condition_ast) = self.check_node(condition.clone());
+            match condition_info.data_type {
+                Some(data_type) if data_type.is_named("bool") => {},
+                Some(data_type) if data_type.is_numeric() => panic!("Cannot use '{}' of type '{}' as a condition at {}, use 'as bool'!", condition.data, data_type, condition.start),
+                data_type => panic!("Cannot use '{}' of type '{}' as a condition at {}!", condition.data, data_type.map_or("unknown".to_string(), |x| x.to_string()), condition.start)
+            }
+
+            match narrowing {
+                Some((variable, true)) => (condition_ast[0].clone(), None, Some(variable), None),
+                Some((variable, false)) => (condition_ast[0].clone(), None, None, Some(variable)),
+                None => (condition_ast[0].clone(), None, None, None)
+            }
+        };
+
+        let body = self.check_block(&position, body, binding, narrowed, false);
+        let else_body = else_body.map(|else_body| self.check_block(&position, else_body, None, else_narrowed, false));
+
+        (NodeInfo::new(Some(DataType::Void), None), vec![
+            position.convert(Node::If { condition: Box::new(condition), body, else_body })
+        ])
+    }
+
+    fn check_unsafe(&mut self, position: Positioned<()>, body: Vec<Positioned<Node>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if self.scope.return_type().is_none() {
+            panic!("Unexpected unsafe block at {}!", position.start);
+        }
+        let body = self.check_block(&position, body, None, None, true);
+        (NodeInfo::new(Some(DataType::Void), None), vec![position.convert(Node::Unsafe(body))])
+    }
+
+    fn check_block(&mut self, position: &Positioned<()>, body: Vec<Positioned<Node>>, binding: Option<VariableSymbol>, narrowed: Option<Arc<Mutex<VariableSymbol>>>, unsafe_code: bool) -> Vec<Positioned<Node>> {
+        // Enter scope (the statements hoisted from the condition go before the if)
+        let hoisted = std::mem::take(&mut self.hoisted);
+        let parent = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
+        self.scope = Scope::new(ScopeType::Block, Some(Box::new(parent)));
+        if let Some(binding) = binding {
+            self.scope.variables.push(Arc::new(Mutex::new(binding)));
+        }
+        self.scope.narrowed.extend(narrowed);
+        self.scope.unsafe_code = unsafe_code;
+
+        let mut new_body = Vec::new();
+        for node in body {
+            let (_, mut node_ast) = self.check_node(node);
+            new_body.append(&mut self.hoisted);
+            new_body.append(&mut node_ast);
+        }
+
+        // Run the deferred expressions and free the class instances owned by the block
+        if !matches!(new_body.last().map(|x| &x.data), Some(Node::Return(_))) {
+            let mut frees = self.cleanup(position, false, None);
+            new_body.append(&mut frees);
+        }
+
+        // Exit scope
+        let scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
+        self.scope = *scope.parent.unwrap();
+        self.hoisted = hoisted;
+        new_body
+    }
+
+    fn check_function_call(&mut self, position: Positioned<()>, name: Positioned<String>, params: Vec<FunctionCallParameter>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let mut selected = self.scope.selected.take();
+        let member = selected.is_some();
+        let scope = match &mut selected {
+            Some(selected) => selected.as_mut(),
+            None => &mut self.scope
+        };
+        let function = scope.get_function(name.data.clone());
+        let variable = scope.get_variable(name.data.clone());
+
+        if let Some(function_arc) = function {
+            let function = function_arc.lock().unwrap().clone();
+
+            // printf("x = {x}") becomes printf("x = %d", x) (only if it takes the values)
+            let external = matches!(function.function_type, FunctionType::ExternalFunction);
+            if let (true, [FunctionCallParameter { value: format }]) = (external && function.variadic && function.name == "printf", params.as_slice()) {
+                if let Node::Interpolation(parts) = format.data.clone() {
+                    let parts = self.check_interpolation_parts(parts);
+                    return (NodeInfo::new(Some(function.data_type.clone()), None), vec![
+                        position.convert(Node::FunctionCall { name, params: vec![FunctionCallParameter { value: format.convert(Node::Interpolation(parts)) }] })
+                    ]);
+                }
+            }
+
+            let expected = function.params.clone();
+            let mut new_params = self.check_call_params(expected, function.variadic, params);
+
+            match (&function.function_type, &function.owner) {
+                (FunctionType::Destructor, _) => panic!("Destructors are called automatically ({})!", position.start),
+                (FunctionType::Function, Some(owner)) if !member => {
+                    // Methods of the current class are called on self
+                    if !self.scope.in_class(owner) || self.scope.is_static() {
+                        panic!("Method '{}' needs an instance of '{}' at {}!", name.data, owner, position.start);
+                    }
+                    new_params.insert(0, FunctionCallParameter { value: position.convert(Node::Value(ValueNode::This)) });
+                },
+                _ => {}
+            }
+
+            (NodeInfo::new(Some(function.data_type.clone()), None), vec![
+                position.convert(Node::FunctionCall { name: name.convert(function.c_name()), params: new_params })
+            ])
+        } else {
+            // Calls through a variable holding a function
+            let Some(variable) = variable else {
+                if !member && (name.data == "ok" || name.data == "err") {
+                    return self.check_result_value(position, name, params);
+                }
+                panic!("Function '{}' not found!", name.data);
+            };
+            if !member {
+                self.capture(&position, &variable);
+            }
+
+            let data_type = variable.lock().unwrap().data_type.clone();
+            match data_type {
+                Some(DataType::Function(expected, return_type)) => {
+                    let new_params = self.check_call_params(expected, false, params);
+                    (NodeInfo::new(Some(*return_type), None), vec![
+                        position.convert(Node::FunctionCall { name: name.clone(), params: new_params })
+                    ])
+                },
+                Some(DataType::Closure(expected, return_type)) => {
+                    if member {
+                        panic!("Cannot call the closure field '{}' at {}, copy it into a variable first!", name.data, position.start);
+                    }
+                    let new_params = self.check_call_params(expected, false, params);
+                    (NodeInfo::new(Some(*return_type), None), vec![
+                        position.convert(Node::ClosureCall { 
+                            value: Box::new(name.convert(Node::Value(ValueNode::VariableCall(name.data.clone())))), 
+                            params: new_params 
+                        })
+                    ])
+                },
+                Some(data_type) => panic!("Cannot call '{}' of type '{}' at {}!", name.data, data_type, position.start),
+                None => panic!("Could not infer type of '{}' at {}!", name.data, position.start)
+            }
+        }
+    }
+
+    // ok(value) and err(error) get their Result type from where they are used
+    fn check_result_value(&mut self, position: Positioned<()>, name: Positioned<String>, mut params: Vec<FunctionCallParameter>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let ok = name.data == "ok";
+        let (data_type, value) = match (params.len(), ok) {
+            (0, true) => (DataType::Void, None),
+            (1, _) => {
+                let (value_info, value_ast) = self.check_node(params.remove(0).value);
+                let Some(data_type) = value_info.data_type else {
+                    panic!("Could not infer the type of the value of '{}' at {}!", name.data, position.start);
+                };
+                (data_type, Some(Box::new(value_ast[0].clone())))
+            },
+            _ => panic!("'{}' takes a single value at {}!", name.data, position.start)
+        };
+
+        (NodeInfo::new(Some(DataType::ResultValue(ok, Box::new(data_type))), None), vec![
+            position.convert(Node::Result { data_type: None, ok, value })
+        ])
+    }
+
+    // "value?" gives the value of a Result or returns its error from the function
+    fn check_try(&mut self, position: Positioned<()>, value: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (value_info, value_ast) = self.check_node(value.clone());
+        let result_type = match value_info.data_type {
+            Some(data_type @ DataType::Generic(_, _)) => data_type,
+            data_type => panic!("Cannot use '?' on '{}' of type '{}' at {}, it is not a Result!", value.data, data_type.map_or("unknown".to_string(), |x| x.to_string()), position.start)
+        };
+        let DataType::Generic(_, params) = result_type.clone() else {
+            unreachable!()
+        };
+
+        // The error must fit in the Result returned by the function
+        let Some(return_type) = self.scope.return_type() else {
+            panic!("Cannot use '?' outside of a function at {}!", position.start);
+        };
+        let compatible = match &return_type {
+            DataType::Generic(_, return_params) => self.check_data_type(return_params[1].clone(), params[1].clone()),
+            _ => false
+        };
+        if !compatible {
+            panic!("Cannot propagate the error '{}' of '{}' from a function returning '{}' at {}!", params[1], value.data, return_type, position.start);
+        }
+
+        // Stores the result in a temporary
+        let name = position.convert(format!("apla_try_{}", self.tries));
+        self.tries += 1;
+        let result = position.convert(Node::Value(ValueNode::VariableCall(name.data.clone())));
+        self.hoisted.push(position.convert(Node::VariableDefinition { 
+            var_type: position.convert(VarType::Constant), 
+            name, 
+            data_type: Some(self.type_expr(&position, &result_type)), 
+            value: Some(Box::new(value_ast[0].clone())), 
+            public: false,
+            attributes: Vec::new()
+        }));
+
+        // Returns the error after the cleanup
+        let mut body = self.cleanup(&position, true, None);
+        body.push(position.convert(Node::Return(Box::new(position.convert(Node::Result { 
+            data_type: Some(self.type_expr(&position, &return_type)), 
+            ok: false, 
+            value: Some(Box::new(Self::field_access(result.clone(), "error"))) 
+        })))));
+        let check = position.convert(Node::If { 
+            condition: Box::new(position.convert(Node::BinaryOperation { 
+                lhs: Box::new(Self::field_access(result.clone(), "ok")), 
+                op: position.convert(Operator::Equal), 
+                rhs: Box::new(position.convert(Node::Value(ValueNode::Decimal("0".to_string())))) 
+            })), 
+            body, 
+            else_body: None 
+        });
+
+        if params[0] == DataType::Void {
+            return (NodeInfo::new(Some(DataType::Void), None), vec![check]);
+        }
+        self.hoisted.push(check);
+        (NodeInfo::new(Some(params[0].clone()), None), vec![Self::field_access(result, "value")])
+    }
+
+    // Types the embedded values (they need a printf format specifier)
+    fn check_interpolation_parts(&mut self, parts: Vec<InterpolationPart>) -> Vec<InterpolationPart> {
+        let mut new_parts = Vec::new();
+        for part in parts {
+            let InterpolationPart::Value(value, _) = part else {
+                new_parts.push(part);
+                continue;
+            };
+            let (value_info, value_ast) = self.check_node(*value.clone());
+            let data_type = match value_info.data_type {
+                Some(data_type @ (DataType::CDecimal | DataType::CFloat | DataType::CString | DataType::Number(_) | DataType::Pointer(_, _) | DataType::Optional(_) | DataType::Null)) => data_type,
+                Some(DataType::Custom(name)) if !name.ends_with("void") => DataType::Custom(name),
+                data_type => panic!("Cannot embed '{}' of type '{}' in a string at {}!", value.data, data_type.map_or("unknown".to_string(), |x| x.to_string()), value.start)
+            };
+            new_parts.push(InterpolationPart::Value(Box::new(value_ast[0].clone()), Some(self.type_expr(&value.convert(()), &data_type))));
+        }
+        new_parts
+    }
+
+    // Interpolated strings are written by snprintf into a buffer on the stack
+    fn check_interpolation(&mut self, position: Positioned<()>, parts: Vec<InterpolationPart>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if self.scope.return_type().is_none() {
+            panic!("Cannot use string interpolation outside of a function at {}!", position.start);
+        }
+        let name = format!("apla_string_{}", self.tries);
+        self.tries += 1;
+        let variable = |name: &String| position.convert(Node::Value(ValueNode::VariableCall(name.clone())));
+
+        // The values are evaluated once (snprintf is called twice)
+        let mut parts = self.check_interpolation_parts(parts);
+        for (i, part) in parts.iter_mut().enumerate() {
+            let InterpolationPart::Value(value, Some(data_type)) = part else {
+                continue;
+            };
+            if matches!(value.data, Node::Value(_)) {
+                continue;
+            }
+            let temporary = format!("{}_{}", name, i);
+            self.hoisted.push(position.convert(Node::VariableDefinition { 
+                var_type: position.convert(VarType::Constant), 
+                name: position.convert(temporary.clone()), 
+                data_type: Some(data_type.clone()), 
+                value: Some(value.clone()), 
+                public: false,
+                attributes: Vec::new()
+            }));
+            **value = variable(&temporary);
+        }
+        let format = position.convert(Node::Interpolation(parts));
+
+        // Length (with the terminating zero) then buffer
+        let length = format!("{}_len", name);
+        let snprintf = |buffer: Positioned<Node>, size: Positioned<Node>| position.convert(Node::FunctionCall { 
+            name: position.convert("snprintf".to_string()), 
+            params: vec![FunctionCallParameter { value: buffer }, FunctionCallParameter { value: size }, FunctionCallParameter { value: format.clone() }] 
+        });
+        self.hoisted.push(position.convert(Node::VariableDefinition { 
+            var_type: position.convert(VarType::Constant), 
+            name: position.convert(length.clone()), 
+            data_type: Some(position.convert(TypeExpr::Named("c_int".to_string()))), 
+            value: Some(Box::new(position.convert(Node::BinaryOperation { 
+                lhs: Box::new(snprintf(position.convert(Node::Value(ValueNode::Null)), position.convert(Node::Value(ValueNode::Decimal("0".to_string()))))), 
+                op: position.convert(Operator::Plus), 
+                rhs: Box::new(position.convert(Node::Value(ValueNode::Decimal("1".to_string())))) 
+            }))), 
+            public: false,
+            attributes: Vec::new()
+        }));
+        self.hoisted.push(position.convert(Node::VariableDefinition { 
+            var_type: position.convert(VarType::Variable), 
+            name: position.convert(name.clone()), 
+            data_type: Some(position.convert(TypeExpr::Array { 
+                inner: Box::new(position.convert(TypeExpr::Named("c_char".to_string()))), 
+                size: Some(position.convert(length.clone())) 
+            })), 
+            value: None, 
+            public: false,
+            attributes: Vec::new()
+        }));
+        self.hoisted.push(snprintf(variable(&name), variable(&length)));
+
+        (NodeInfo::new(Some(DataType::CString), None), vec![variable(&name)])
+    }
+
+    fn check_lambda(&mut self, position: Positioned<()>, params: Vec<FunctionDefinitionParameter>, return_type: Option<Positioned<TypeExpr>>, body: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        // Enter Scope
+        let parent = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
+        self.scope = Scope::new(ScopeType::Lambda, Some(Box::new(parent)));
+
+        let mut param_types = Vec::new();
+        for param in params.iter() {
+            let data_type = self.resolve_type(&param.data_type);
+            param_types.push(data_type.clone());
+            self.scope.variables.push(Arc::new(Mutex::new(VariableSymbol::new(VarType::Constant, param.name.data.clone(), Some(data_type), true))));
+        }
+
+        // Check body (the value of the body is returned)
+        let (body_info, body_ast) = self.check_node(body);
+        let found = body_info.data_type.clone();
+        let data_type = if let Some(return_type) = &return_type {
+            let return_type = self.resolve_type(return_type);
+            self.infer_and_check(body_info, return_type)
+        } else if let Some(found) = &found {
+            found.normalized()
+        } else {
+            panic!("Cannot infer the return type of the lambda at {}!", position.start);
+        };
+        match data_type {
+            DataType::Array(_, _) => panic!("A lambda cannot return a fixed-size array at {}, its memory is on the stack!", position.start),
+            DataType::Closure(_, _) => panic!("A lambda cannot return a capturing lambda at {}!", position.start),
+            _ => {}
+        }
+
+        // TODO: check if more than 1 value (in the ast)
+        let body = self.coerce(body_ast[0].clone(), found, &data_type);
+        if let Some(slice) = self.stack_slice(&body) {
+            panic!("A lambda cannot return '{}' at {}, it views memory on the stack!", slice.data, slice.start);
+        }
+
+        // Exit scope
+        let scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
+        self.scope = *scope.parent.unwrap();
+
+        let captures: Vec<FunctionDefinitionParameter> = scope.captures.iter().map(|variable| {
+            let variable = variable.lock().unwrap();
+            FunctionDefinitionParameter { 
+                name: position.convert(variable.name.clone()), 
+                data_type: self.type_expr(&position, variable.data_type.as_ref().unwrap()) 
+            }
+        }).collect();
+
+        // Lambdas without captures are plain functions
+        let lambda_type = if captures.is_empty() {
+            DataType::Function(param_types, Box::new(data_type.clone()))
+        } else {
+            DataType::Closure(param_types, Box::new(data_type.clone()))
+        };
+
+        (NodeInfo::new(Some(lambda_type), None), vec![
+            position.convert(Node::Lambda { 
+                params, 
+                return_type: Some(return_type.unwrap_or_else(|| self.type_expr(&position, &data_type))), 
+                body: Box::new(body), 
+                captures 
+            })
+        ])
+    }
+
+    fn check_array(&mut self, position: Positioned<()>, values: Vec<Positioned<Node>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let mut element_type: Option<DataType> = None;
+        let mut new_values = Vec::new();
+        for value in values {
+            let (value_info, value_ast) = self.check_node(value);
+            let found = value_info.data_type.clone();
+
+            element_type = Some(if let Some(expected) = element_type {
+                let data_type = self.infer_and_check(value_info, expected.clone());
+                // Prefer the concrete C type over the one of a literal
+                match (expected, found) {
+                    (DataType::CDecimal | DataType::CFloat, Some(found)) => found,
+                    _ => data_type
+                }
+            } else if let Some(found) = found {
+                found
+            } else {
+                panic!("Cannot infer the type of the array element at {}!", position.start)
+            });
+
+            // TODO: check if more than 1 value (in the ast)
+            new_values.push(value_ast[0].clone());
+        }
+
+        let Some(element_type) = element_type else {
+            panic!("Cannot infer the element type of an empty array at {}!", position.start);
+        };
+        let data_type = DataType::Array(Box::new(element_type), new_values.len());
+
+        (NodeInfo::new(Some(data_type.clone()), None), vec![
+            position.convert(Node::Array { data_type: Some(self.type_expr(&position, &data_type)), values: new_values })
+        ])
+    }
+
+    fn check_tuple(&mut self, position: Positioned<()>, values: Vec<Positioned<Node>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let mut elements = Vec::new();
+        let mut new_values = Vec::new();
+        for value in values {
+            let (value_info, value_ast) = self.check_node(value.clone());
+            let Some(element) = value_info.data_type else {
+                panic!("Cannot infer the type of the tuple element '{}' at {}!", value.data, value.start);
+            };
+            self.check_tuple_element(&element, &value.convert(()));
+            elements.push(element);
+            // TODO: check if more than 1 value (in the ast)
+            new_values.push(value_ast[0].clone());
+        }
+        let data_type = DataType::Tuple(elements);
+
+        (NodeInfo::new(Some(data_type.clone()), None), vec![
+            position.convert(Node::Tuple { data_type: Some(self.type_expr(&position, &data_type)), values: new_values })
+        ])
+    }
+
+    // Tuples are copied by value, so they cannot own class instances
+    fn check_tuple_element(&mut self, data_type: &DataType, position: &Positioned<()>) {
+        let valid = match data_type {
+            DataType::Void | DataType::Null | DataType::None | DataType::ResultValue(_, _) => false,
+            DataType::Array(_, _) | DataType::Closure(_, _) => false,
+            data_type => self.is_class(&Some(data_type.clone())).is_none()
+        };
+        if !valid {
+            panic!("Type '{}' cannot be part of a tuple at {}!", data_type, position.start);
+        }
+    }
+
+    // t.0 is the field _0 of the tuple struct
+    fn check_tuple_access(&mut self, position: Positioned<()>, lhs_info: NodeInfo, lhs: Positioned<Node>, elements: Vec<DataType>, rhs: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let index = match &rhs.data {
+            Node::Value(ValueNode::Decimal(index)) => index.parse::<usize>().ok(),
+            _ => None
+        };
+        let Some((index, element)) = index.and_then(|index| elements.get(index).map(|element| (index, element.clone()))) else {
+            panic!("'{}' of type '{}' has no element '{}' at {}!", lhs.data, DataType::Tuple(elements), rhs.data, rhs.start);
+        };
+
+        // The element can be assigned if the tuple can
+        let var_type = match lhs_info.symbol {
+            Some(Symbol::Variable(variable)) => variable.lock().unwrap().var_type.clone(),
+            _ => VarType::Constant
+        };
+        let variable = VariableSymbol::new(var_type, format!("{}.{}", lhs.data, index), Some(element.clone()), true);
+        (NodeInfo::new(Some(element), Some(Symbol::Variable(Arc::new(Mutex::new(variable))))), vec![
+            position.convert(Node::BinaryOperation { 
+                lhs: Box::new(lhs), 
+                op: position.convert(Operator::FieldAccess), 
+                rhs: Box::new(rhs.convert(Node::Value(ValueNode::VariableCall(format!("_{}", index))))) 
+            })
+        ])
+    }
+
+    // The tuple is stored in a temporary, then each name gets its element
+    fn check_destructuring(&mut self, position: Positioned<()>, var_type: Positioned<VarType>, names: Vec<Positioned<String>>, data_type: Option<Positioned<TypeExpr>>, value: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if !matches!(self.scope.scope, ScopeType::Function(_, _) | ScopeType::Lambda | ScopeType::Block) {
+            panic!("Destructuring is only allowed inside of functions at {}!", position.start);
+        }
+
+        let tuple = position.convert(format!("apla_tuple_{}", self.tries));
+        self.tries += 1;
+        let (_, mut ast) = self.check_node(position.convert(Node::VariableDefinition { 
+            var_type: position.convert(VarType::Constant), 
+            name: tuple.clone(), 
+            data_type, 
+            value: Some(Box::new(value.clone())), 
+            public: false,
+            attributes: Vec::new()
+        }));
+        let found = self.scope.variables.last().unwrap().lock().unwrap().data_type.clone();
+        match found {
+            Some(DataType::Tuple(elements)) if elements.len() == names.len() => {},
+            Some(found) => panic!("Cannot destructure '{}' of type '{}' into {} names at {}!", value.data, found, names.len(), value.start),
+            None => panic!("Cannot infer the type of '{}' at {}!", value.data, value.start)
+        }
+
+        for (index, name) in names.into_iter().enumerate() {
+            let element = name.convert(Node::BinaryOperation { 
+                lhs: Box::new(name.convert(Node::Value(ValueNode::VariableCall(tuple.data.clone())))), 
+                op: name.convert(Operator::MemberAccess), 
+                rhs: Box::new(name.convert(Node::Value(ValueNode::Decimal(index.to_string())))) 
+            });
+            let (_, mut element_ast) = self.check_node(name.convert(Node::VariableDefinition { 
+                var_type: var_type.clone(), 
+                name: name.clone(), 
+                data_type: None, 
+                value: Some(Box::new(element)), 
+                public: false,
+                attributes: Vec::new()
+            }));
+            ast.append(&mut element_ast);
+        }
+
+        (NodeInfo::new(Some(DataType::Void), None), ast)
+    }
+
+    fn check_index(&mut self, position: Positioned<()>, value: Positioned<Node>, index: Positioned<Node>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        let (value_info, value_ast) = self.check_node(value);
+        let (index_info, index_ast) = self.check_node(index);
+        self.infer_and_check(index_info, DataType::CDecimal);
+
+        // TODO: check if more than 1 value (in the ast)
+        let value = value_ast[0].clone();
+        let index = index_ast[0].clone();
+
+        let (element_type, value, length) = match value_info.data_type {
+            Some(DataType::Array(element_type, size)) => {
+                // Constant indices can be checked right away
+                if let Node::Value(ValueNode::Decimal(constant)) = &index.data {
+                    if constant.parse::<usize>().map_or(true, |constant| constant >= size) {
+                        panic!("Index {} out of bounds for length {} at {}!", constant, size, index.start);
+                    }
+                }
+                let length = position.convert(Node::Value(ValueNode::Decimal(size.to_string())));
+                (*element_type, value, length)
+            },
+            Some(DataType::Slice(element_type)) => {
+                let length = Self::field_access(value.clone(), "len");
+                (*element_type, Self::field_access(value, "ptr"), length)
+            },
+            Some(data_type) => panic!("Cannot index into '{:?}'!", data_type),
+            None => panic!("Could not infer type of indexed value!")
+        };
+
+        (NodeInfo::new(Some(element_type), value_info.symbol), vec![
+            position.convert(Node::Index { value: Box::new(value), index: Box::new(index), length: Some(Box::new(length)) })
+        ])
+    }
+
+    fn check_include(&mut self, position: Positioned<()>, path: Positioned<String>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        // TODO: find a way to check if the path is valid and exists.
+        self.includes.push((position, path));
+        (NodeInfo::new(Some(DataType::Void), None), vec![
+            // No need to add the node (post processed)
+        ])
+    }
+
+    fn check_import(&mut self, position: Positioned<()>, module: Positioned<String>, names: Option<Vec<Positioned<String>>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if !matches!(self.scope.scope, ScopeType::Root) {
+            panic!("Imports are only allowed at the top of a file ({})!", position.start);
+        }
+
+        let Some(module_symbol) = self.modules.iter().find(|x| x.lock().unwrap().name == module.data).cloned() else {
+            panic!("Module '{}' not found at {}!", module.data, module.start);
+        };
+        self.imports.push(module.data.clone());
+
+        if let Some(names) = &names {
+            // Selected symbols are used without the module name
+            for name in names {
+                if self.scope.symbol_exists(name.data.clone()) {
+                    panic!("Symbol '{}' already exists at {}!", name.data, name.start);
+                }
+
+                let symbol = module_symbol.lock().unwrap();
+                if symbol.is_private(&name.data) {
+                    panic!("'{}' is private to module '{}' at {}!", name.data, module.data, name.start);
+                }
+                if let Some(function) = symbol.functions.iter().find(|x| x.lock().unwrap().name == name.data) {
+                    self.scope.functions.push(function.clone());
+                } else if let Some(class) = symbol.classes.iter().find(|x| x.lock().unwrap().name == name.data) {
+                    self.scope.classes.push(class.clone());
+                } else if let Some(alias) = symbol.aliases.iter().find(|x| x.name == name.data) {
+                    self.scope.aliases.push(alias.clone());
+                } else if let Some(variable) = symbol.variables.iter().find(|x| x.lock().unwrap().name == name.data) {
+                    self.scope.variables.push(variable.clone());
+                } else {
+                    panic!("Symbol '{}' not found in module '{}' at {}!", name.data, module.data, name.start);
+                }
+                self.imported.push(name.data.clone());
+            }
+        } else {
+            if self.scope.symbol_exists(module.data.clone()) {
+                panic!("Symbol '{}' already exists at {}!", module.data, module.start);
+            }
+            self.scope.modules.push(module_symbol);
+        }
+
+        (NodeInfo::new(Some(DataType::Void), None), vec![
+            position.convert(Node::Import { module, names })
+        ])
+    }
+
+    // Aliases are replaced by their type (typedefs in C)
+    fn check_type_alias(&mut self, position: Positioned<()>, name: Positioned<String>, data_type: Positioned<TypeExpr>, public: bool) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if !matches!(self.scope.scope, ScopeType::Root) {
+            panic!("Type aliases are only allowed at the top level ({})!", position.start);
+        }
+        if self.scope.symbol_exists(name.data.clone()) {
+            panic!("Symbol '{}' already exists at {}!", name.data, name.start);
+        }
+
+        let resolved = self.resolve_type(&data_type);
+        self.scope.aliases.push(AliasSymbol { name: name.data.clone(), data_type: resolved, public });
+
+        (NodeInfo::new(Some(DataType::Void), None), vec![
+            position.convert(Node::TypeAlias { name, data_type, public })
+        ])
+    }
+
+    // Types of C headers: opaque classes (used behind pointers) or structs with their fields
+    fn check_extern_type(&mut self, position: Positioned<()>, name: Positioned<String>, fields: Option<Vec<Positioned<Node>>>, public: bool, attributes: Vec<Attribute>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if !matches!(self.scope.scope, ScopeType::Root) {
+            panic!("Extern types are only allowed at the top level ({})!", position.start);
+        }
+        if self.scope.symbol_exists(name.data.clone()) {
+            panic!("Symbol '{}' already exists at {}!", name.data, name.start);
+        }
+
+        let mut class = ClassSymbol::new(name.data.clone(), public);
+        class.kind = ClassKind::Opaque;
+        if let Some(fields) = &fields {
+            self.check_attributes(&attributes, &["typedef"], "extern structs", public);
+            class.kind = ClassKind::Struct;
+            class.typedef = attributes.iter().any(|attribute| attribute.name.data == "typedef");
+            for field in fields {
+                let Node::VariableDefinition { var_type, name: field_name, data_type: Some(data_type), value: None, public: false, attributes } = &field.data else {
+                    panic!("Expected a field without value ('var name: type') in extern struct '{}' at {}!", name.data, field.start);
+                };
+                self.check_attributes(attributes, &[], "extern struct fields", false);
+                if class.get_field(field_name.data.clone()).is_some() {
+                    panic!("Field '{}' already exists at {}!", field_name.data, field_name.start);
+                }
+                let data_type = self.resolve_type(data_type);
+                class.fields.push(Arc::new(Mutex::new(VariableSymbol::new(var_type.data.clone(), field_name.data.clone(), Some(data_type), true))));
+            }
+        }
+        self.scope.classes.push(Arc::new(Mutex::new(class)));
+
+        let node = match fields {
+            Some(fields) => Node::ExternStruct { name, fields, public, attributes },
+            None => Node::ExternClass { name, public }
+        };
+        (NodeInfo::new(Some(DataType::Void), None), vec![position.convert(node)])
+    }
+
+    fn check_extern_constant(&mut self, position: Positioned<()>, name: Positioned<String>, data_type: Positioned<TypeExpr>, public: bool) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if !matches!(self.scope.scope, ScopeType::Root) {
+            panic!("Extern constants are only allowed at the top level ({})!", position.start);
+        }
+        if self.scope.symbol_exists(name.data.clone()) {
+            panic!("Symbol '{}' already exists at {}!", name.data, name.start);
+        }
+
+        let mut symbol = VariableSymbol::new(VarType::Constant, name.data.clone(), Some(self.resolve_type(&data_type)), true);
+        symbol.public = public;
+        self.scope.variables.push(Arc::new(Mutex::new(symbol)));
+
+        (NodeInfo::new(Some(DataType::Void), None), vec![
+            position.convert(Node::ExternConstant { name, data_type, public })
+        ])
+    }
+
+    // The C code is not checked, only the variables it references
+    fn check_inline_c(&mut self, position: Positioned<()>, lines: Vec<Positioned<Node>>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        if matches!(self.scope.scope, ScopeType::Root | ScopeType::Class(_) | ScopeType::Module(_)) {
+            panic!("Inline C is only allowed inside of functions at {}!", position.start);
+        }
+
+        let mut new_lines = Vec::new();
+        for line in lines {
+            let parts = match line.data.clone() {
+                Node::Value(ValueNode::String(code)) => vec![InterpolationPart::Text(code)],
+                Node::Interpolation(parts) => parts,
+                _ => panic!("Inline C expects strings of code at {}!", line.start)
+            };
+
+            let mut new_parts = Vec::new();
+            for part in parts {
+                let InterpolationPart::Value(value, None) = part else {
+                    new_parts.push(part);
+                    continue;
+                };
+                if !matches!(value.data, Node::Value(ValueNode::VariableCall(_) | ValueNode::This)) {
+                    panic!("Inline C can only reference variables, found '{}' at {}!", value.data, value.start);
+                }
+                let (value_info, value_ast) = self.check_node(*value.clone());
+                let Some(Symbol::Variable(variable)) = value_info.symbol else {
+                    panic!("'{}' is not a variable at {}!", value.data, value.start);
+                };
+                // The C code may assign it
+                variable.lock().unwrap().initialized = true;
+                new_parts.push(InterpolationPart::Value(Box::new(value_ast[0].clone()), None));
+            }
+            new_lines.push(line.convert(Node::Interpolation(new_parts)));
+        }
+
+        (NodeInfo::new(Some(DataType::Void), None), vec![position.convert(Node::InlineC(new_lines))])
+    }
+
+    // Attributes allowed on the definition, their values are checked here and used by the translator
+    fn check_attributes(&self, attributes: &[Attribute], allowed: &[&str], definitions: &str, public: bool) {
+        let mut names = Vec::new();
+        for attribute in attributes {
+            let name = attribute.name.data.as_str();
+            // None if the value is optional
+            let expects_value = match name {
+                "inline" | "packed" | "cold" | "typedef" => Some(false),
+                "export" => Some(true),
+                "deprecated" => None,
+                _ => panic!("Unknown attribute '@{}' at {}!", name, attribute.name.start)
+            };
+            if !allowed.contains(&name) {
+                panic!("Attribute '@{}' cannot be used on {} at {}!", name, definitions, attribute.name.start);
+            }
+            if names.contains(&name) {
+                panic!("Duplicate attribute '@{}' at {}!", name, attribute.name.start);
+            }
+            names.push(name);
+
+            match (expects_value, &attribute.value) {
+                (Some(true), None) => panic!("Attribute '@{}' expects a value at {}!", name, attribute.name.start),
+                (Some(false), Some(value)) => panic!("Attribute '@{}' does not take a value at {}!", name, value.start),
+                _ => {}
+            }
+            if name == "export" {
+                if !public {
+                    panic!("Only public definitions can be exported at {}!", attribute.name.start);
+                }
+                let value = attribute.value.as_ref().unwrap();
+                let valid = value.data.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') && value.data.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !valid {
+                    panic!("'{}' is not a valid C name at {}!", value.data, value.start);
+                }
+            }
+        }
+    }
+
+    fn check_class_definition(&mut self, position: Positioned<()>, name: Positioned<String>, body: Vec<Positioned<Node>>, public: bool, attributes: Vec<Attribute>) -> (NodeInfo, Vec<Positioned<Node>>) {
+        // check if class doesn't exists
+        if self.scope.symbol_exists(name.data.clone()) {
+            panic!("Symbol already exists");
+        }
+        self.check_linked_name(&name);
+        self.check_attributes(&attributes, &["packed"], "classes", public);
+        
+        // Add Symbol
+        let class = Arc::new(Mutex::new(ClassSymbol::new(name.data.clone(), public)));
+        self.scope.classes.push(class.clone());
+
+        // Enter scope
+        let mut scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
+        self.scope = Scope::new(ScopeType::Class(name.data.clone()), Some(Box::new(scope)));
+        
+        // Add Self
+        self.scope.variables.push(Arc::new(Mutex::new(VariableSymbol::new(VarType::Constant, "self".to_string(), Some(DataType::Custom(name.data.clone())), true))));
+
+        let member = |node: &Node| match node {
+            Node::Static(node) => matches!(node.data, Node::VariableDefinition { .. } | Node::FunctionDefinition { constructor: false, .. }),
+            node => matches!(node, Node::VariableDefinition { .. } | Node::FunctionDefinition { .. })
+        };
+        if body.iter().any(|node| !member(&node.data)) {
+            panic!("Unexpected node")
+        }
+
+        // Check the fields first (defaults are assigned at the start of every constructor)
+        let mut new_body = Vec::new();
+        let mut defaults = Vec::new();
+        for node in body.iter().filter(|node| matches!(node.data, Node::VariableDefinition { .. })) {
+            let (_, mut ast) = self.check_node(node.clone());
+            self.hoisted.clear(); // Checked again in the constructors
+            if let Node::VariableDefinition { name: field, value, .. } = &mut ast[0].data {
+                if let Some(value) = value.take() {
+                    let Node::VariableDefinition { value: Some(written), .. } = &node.data else {
+                        unreachable!()
+                    };
+                    // The class file only sees the public symbols of the module (through its header)
+                    if let Some(private) = self.private_global(&value) {
+                        panic!("Default of field '{}' cannot use the private global '{}' at {}!", field.data, private.data, private.start);
+                    }
+                    // Constants are folded
+                    let written = match value.data {
+                        Node::Value(_) => value.clone(),
+                        _ => written.clone()
+                    };
+                    defaults.push(value.convert(Node::BinaryOperation { 
+                        lhs: Box::new(field.convert(Node::BinaryOperation { 
+                            lhs: Box::new(field.convert(Node::Value(ValueNode::This))), 
+                            op: field.convert(Operator::MemberAccess), 
+                            rhs: Box::new(field.convert(Node::Value(ValueNode::VariableCall(field.data.clone())))) 
+                        })), 
+                        op: value.convert(Operator::Assignment), 
+                        rhs: written 
+                    }));
+                }
+            }
+            new_body.append(&mut ast);
+            // Add variable to symbol (last symbol)
+            let field_symbol = self.scope.variables.last().cloned().unwrap();
+            class.lock().unwrap().fields.push(field_symbol);
+        }
+
+        // Static fields are globals (not visible through self)
+        for node in body.iter() {
+            let Node::Static(node) = &node.data else {
+                continue;
+            };
+            let Node::VariableDefinition { var_type, name: field, value, .. } = &node.data else {
+                continue;
+            };
+            if class.lock().unwrap().get_field(field.data.clone()).is_some() {
+                panic!("Symbol '{}' already exists at {}!", field.data, field.start);
+            }
+            if value.is_none() && var_type.data == VarType::Constant {
+                panic!("Constant '{}' must be initialized at {}!", field.data, field.start);
+            }
+
+            self.scope.static_code = true;
+            let (_, ast) = self.check_node(*node.clone());
+            self.scope.static_code = false;
+            if let Node::VariableDefinition { value: Some(value), .. } = &ast[0].data {
+                if !Self::is_constant(&value.data) {
+                    panic!("Static field '{}' needs a compile-time initializer at {}!", field.data, value.start);
+                }
+            }
+            new_body.push(node.convert(Node::Static(Box::new(ast[0].clone()))));
+            let static_symbol = self.scope.variables.pop().unwrap();
+            static_symbol.lock().unwrap().initialized = true;
+            class.lock().unwrap().statics.push(static_symbol);
+        }
+
+        // A class without constructors gets a default one
+        let mut functions: Vec<Positioned<Node>> = body.into_iter().filter(|node| match &node.data {
+            Node::Static(node) => matches!(node.data, Node::FunctionDefinition { .. }),
+            node => matches!(node, Node::FunctionDefinition { .. })
+        }).collect();
+        if !functions.iter().any(|node| matches!(node.data, Node::FunctionDefinition { constructor: true, .. })) {
+            functions.insert(0, name.convert(Node::FunctionDefinition { 
+                name: name.convert("create".to_string()), 
+                return_type: None, 
+                params: Vec::new(), 
+                body: Some(Vec::new()), 
+                constructor: true, 
+                constant: false, 
+                variadic: false, 
+                public: true,
+                attributes: Vec::new()
+            }));
+        }
+
+        for mut node in functions {
+            let constructor = matches!(node.data, Node::FunctionDefinition { constructor: true, .. });
+            if let Node::FunctionDefinition { body: Some(body), constructor: true, .. } = &mut node.data {
+                body.splice(0..0, defaults.iter().cloned());
+            }
+
+            // Constant fields are assigned once by each constructor
+            let fields = class.lock().unwrap().fields.clone();
+            for field in fields.iter() {
+                let mut field = field.lock().unwrap();
+                if field.var_type == VarType::Constant {
+                    field.initialized = !constructor;
+                }
+            }
+
+            // Static functions are called on the class
+            let (_, mut ast) = if let Node::Static(function) = node.data.clone() {
+                self.scope.static_code = true;
+                let (info, ast) = self.check_node(*function);
+                self.scope.static_code = false;
+                self.scope.functions.last().unwrap().lock().unwrap().function_type = FunctionType::Static;
+                (info, vec![node.convert(Node::Static(Box::new(ast[0].clone())))])
+            } else {
+                self.check_node(node)
+            };
+            new_body.append(&mut ast);
+            // Add function to symbol (last symbol)
+            let function_symbol = self.scope.functions.last().cloned().unwrap();
+            class.lock().unwrap().functions.push(function_symbol);
+        }
+
+        // Exit the scope
+        scope = std::mem::replace(&mut self.scope, Scope::new(ScopeType::Root, None));
+        self.scope = *scope.parent.unwrap();
+
+        (NodeInfo::new(Some(DataType::Void), None), vec![
+            position.convert(Node::ClassDefinition { name: name.clone(), body: new_body, public, attributes })
+        ])
+    }
+
+    // Private variable or function of the module used in a checked expression
+    fn private_global(&self, node: &Positioned<Node>) -> Option<Positioned<String>> {
+        let module = self.scope.parent.as_ref()?;
+        match &node.data {
+            Node::Value(ValueNode::VariableCall(name)) => module.variables.iter()
+                .any(|variable| variable.lock().unwrap().name == *name && !variable.lock().unwrap().public)
+                .then(|| node.convert(name.clone())),
+            Node::FunctionCall { name, params } => {
+                if module.functions.iter().any(|function| function.lock().unwrap().c_name() == name.data && !function.lock().unwrap().public) {
+                    return Some(name.clone());
+                }
+                params.iter().find_map(|param| self.private_global(&param.value))
+            },
+            Node::BinaryOperation { lhs, op, rhs } => match op.data {
+                Operator::MemberAccess | Operator::FieldAccess => self.private_global(lhs),
+                _ => self.private_global(lhs).or_else(|| self.private_global(rhs))
+            },
+            Node::UnaryOperation { value, .. } |
+            Node::Cast { value, .. } => self.private_global(value),
+            Node::Index { value, index, .. } => self.private_global(value).or_else(|| self.private_global(index)),
+            Node::Array { values, .. } |
+            Node::Tuple { values, .. } => values.iter().find_map(|value| self.private_global(value)),
+            _ => None
+        }
     }
 
     // Should return the generated AST from the node + the info
@@ -707,18 +3513,60 @@ impl Checker {
                 self.check_value(node.convert(value)),
             Node::BinaryOperation { lhs, op, rhs } => 
                 self.check_binary_operation(node.convert(()), *lhs, op, *rhs),
-            Node::VariableDefinition { var_type, name, data_type, value } => 
-                self.check_variable_definition(node.convert(()), var_type, name, data_type, value),
-            Node::FunctionDefinition { name, return_type, params, body, constructor } => 
-                self.check_function_definition(node.convert(()), name, return_type, params, body, constructor),
+            Node::UnaryOperation { op, value } => 
+                self.check_unary_operation(node.convert(()), op, *value),
+            Node::VariableDefinition { var_type, name, data_type, value, public, attributes } => 
+                self.check_variable_definition(node.convert(()), var_type, name, data_type, value, public, attributes),
+            Node::FunctionDefinition { name, return_type, params, body, constructor, constant, variadic, public, attributes } => 
+                self.check_function_definition(node.convert(()), name, return_type, params, body, constructor, constant, variadic, public, attributes),
             Node::Return(value) => 
                 self.check_return(node.convert(()), *value),
+            Node::Defer(value) => 
+                self.check_defer(node.convert(()), *value),
+            Node::If { condition, body, else_body } => 
+                self.check_if(node.convert(()), *condition, body, else_body),
+            Node::Try(value) => 
+                self.check_try(node.convert(()), *value),
+            Node::Cast { value, data_type } => 
+                self.check_cast(node.convert(()), *value, data_type),
+            Node::Unsafe(body) => 
+                self.check_unsafe(node.convert(()), body),
+            Node::InlineC(lines) => 
+                self.check_inline_c(node.convert(()), lines),
+            Node::Interpolation(parts) => 
+                self.check_interpolation(node.convert(()), parts),
+            Node::Result { .. } => panic!("Result literals are only generated by the checker!"),
             Node::FunctionCall { name, params } => 
                 self.check_function_call(node.convert(()), name, params),
             Node::Include(path) => 
                 self.check_include(node.convert(()), path),
-            Node::ClassDefinition { name, body } => 
-                self.check_class_definition(node.convert(()), name, body)
+            Node::Import { module, names } => 
+                self.check_import(node.convert(()), module, names),
+            Node::TypeAlias { name, data_type, public } => 
+                self.check_type_alias(node.convert(()), name, data_type, public),
+            Node::ClassDefinition { name, body, public, attributes } => 
+                self.check_class_definition(node.convert(()), name, body, public, attributes),
+            Node::ExternClass { name, public } => 
+                self.check_extern_type(node.convert(()), name, None, public, Vec::new()),
+            Node::ExternStruct { name, fields, public, attributes } => 
+                self.check_extern_type(node.convert(()), name, Some(fields), public, attributes),
+            Node::ExternConstant { name, data_type, public } => 
+                self.check_extern_constant(node.convert(()), name, data_type, public),
+            Node::Static(_) => panic!("Static members are only allowed in classes ({})!", node.start),
+            Node::Array { data_type: _, values } => 
+                self.check_array(node.convert(()), values),
+            Node::Tuple { data_type: _, values } => 
+                self.check_tuple(node.convert(()), values),
+            Node::Destructuring { var_type, names, data_type, value } => 
+                self.check_destructuring(node.convert(()), var_type, names, data_type, *value),
+            Node::Index { value, index, length: _ } => 
+                self.check_index(node.convert(()), *value, *index),
+            Node::Slice { .. } => 
+                panic!("Slices are only generated by the checker!"),
+            Node::Lambda { params, return_type, body, captures: _ } => 
+                self.check_lambda(node.convert(()), params, return_type, *body),
+            Node::ClosureCall { .. } => 
+                panic!("Closure calls are only generated by the checker!")
         }
     }
 
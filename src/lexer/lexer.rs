@@ -36,7 +36,16 @@ impl Lexer {
         Positioned::new(data, start, end)
     }
 
-    fn make_number(&mut self) -> Positioned<Token> {
+    fn make_double<T>(&mut self, data: T) -> Positioned<T> {
+        let start = self.pos.clone();
+        self.advance();
+        let mut end = self.pos.clone();
+        end.advance(self.current());
+        Positioned::new(data, start, end)
+    }
+
+    // Numbers after a dot are tuple indices (t.0.1), not fractions
+    fn make_number(&mut self, fraction: bool) -> Positioned<Token> {
         let mut buf = String::new();
         let start = self.pos.clone();
 
@@ -47,6 +56,17 @@ impl Lexer {
             current = self.current();
         }
 
+        if fraction && current == '.' && self.peek(1).is_ascii_digit() {
+            buf.push(current);
+            self.advance();
+            current = self.current();
+            while current.is_ascii_digit() {
+                buf.push(current);
+                self.advance();
+                current = self.current();
+            }
+        }
+
         let end = self.pos.clone();
 
         return Positioned::new(Token::Decimal(buf), start, end);
@@ -72,28 +92,69 @@ impl Lexer {
         }, start, end)
     }
 
-    fn make_string(&mut self) -> Result<Positioned<Token>, LexerError> {
+    // "{expr}" embeds an expression, "{{" and "}}" are braces
+    fn make_string(&mut self) -> Result<Vec<Positioned<Token>>, LexerError> {
+        let mut tokens = Vec::new();
         let mut buf = String::new();
         let start = self.pos.clone();
+        let mut part_start = self.pos.clone();
         self.advance();
 
         let mut current = self.current();
         while current != '"' {
-            if current == '\0' {
-                return Err(LexerError::UnexpectedEOF);
+            match current {
+                '\0' => return Err(LexerError::UnexpectedEOF),
+                // Escape sequences are kept for C
+                '\\' if self.peek(1) != '\0' => {
+                    buf.push(current);
+                    self.advance();
+                    buf.push(self.current());
+                },
+                '{' | '}' if self.peek(1) == current => {
+                    buf.push(current);
+                    self.advance();
+                },
+                '{' => {
+                    if !buf.is_empty() {
+                        tokens.push(Positioned::new(Token::String(std::mem::take(&mut buf)), part_start, self.pos.clone()));
+                    }
+                    tokens.push(self.make_single(Token::LeftBrace));
+                    self.advance();
+                    self.tokenize_until(&mut tokens, true)?;
+                    tokens.push(self.make_single(Token::RightBrace));
+                    self.advance();
+                    part_start = self.pos.clone();
+                    current = self.current();
+                    continue;
+                },
+                _ => buf.push(current)
             }
-            buf.push(current);
             self.advance();
             current = self.current();
         }
         self.advance();
         let end = self.pos.clone();
 
-        return Ok(Positioned::new(Token::String(buf), start, end));
+        if tokens.is_empty() {
+            return Ok(vec![Positioned::new(Token::String(buf), start, end)]);
+        }
+        if !buf.is_empty() {
+            tokens.push(Positioned::new(Token::String(buf), part_start, end.clone()));
+        }
+        tokens.insert(0, Positioned::new(Token::InterpolationStart, start.clone(), start));
+        tokens.push(Positioned::new(Token::InterpolationEnd, end.clone(), end));
+        Ok(tokens)
     }
 
     pub fn tokenize(&mut self) -> Result<Vec<Positioned<Token>>, LexerError> {
         let mut tokens = Vec::new();
+        self.tokenize_until(&mut tokens, false)?;
+        Ok(tokens)
+    }
+
+    // Stops at the end of the file or at the brace closing an embedded expression
+    fn tokenize_until(&mut self, tokens: &mut Vec<Positioned<Token>>, interpolation: bool) -> Result<(), LexerError> {
+        let mut braces = 0;
         let mut space_count;
         let mut space_start = self.pos.clone();
 
@@ -118,7 +179,8 @@ impl Lexer {
 
             match current {
                 '0'..='9'=> {
-                    tokens.push(self.make_number());
+                    let fraction = !tokens.last().is_some_and(|token: &Positioned<Token>| token.data == Token::Dot);
+                    tokens.push(self.make_number(fraction));
                     continue;
                 }
                 'a'..='z' | 'A'..='Z' => {
@@ -126,18 +188,42 @@ impl Lexer {
                     continue;
                 }
                 '"' => {
-                    tokens.push(self.make_string()?);
+                    tokens.append(&mut self.make_string()?);
                     continue;
                 }
                 '+' => tokens.push(self.make_single(Token::Plus)),
                 '-' => tokens.push(self.make_single(Token::Dash)),
                 '*' => tokens.push(self.make_single(Token::Star)),
                 '/' => tokens.push(self.make_single(Token::Slash)),
+                '&' if self.peek(1) == '&' => tokens.push(self.make_double(Token::DoubleAmpersand)),
+                '&' => tokens.push(self.make_single(Token::Ampersand)),
+                '|' if self.peek(1) == '|' => tokens.push(self.make_double(Token::DoublePipe)),
+                '@' => tokens.push(self.make_single(Token::At)),
                 '(' => tokens.push(self.make_single(Token::LeftParenthesis)),
                 ')' => tokens.push(self.make_single(Token::RightParenthesis)),
+                '[' => tokens.push(self.make_single(Token::LeftBracket)),
+                ']' => tokens.push(self.make_single(Token::RightBracket)),
+                '{' => {
+                    braces += 1;
+                    tokens.push(self.make_single(Token::LeftBrace));
+                },
+                '}' if interpolation && braces == 0 => break,
+                '}' => {
+                    braces -= 1;
+                    tokens.push(self.make_single(Token::RightBrace));
+                },
+                ';' => tokens.push(self.make_single(Token::SemiColon)),
+                '?' => tokens.push(self.make_single(Token::Question)),
                 ':' => tokens.push(self.make_single(Token::Colon)),
                 ',' => tokens.push(self.make_single(Token::Comma)),
+                '.' if self.peek(1) == '.' && self.peek(2) == '.' => {
+                    let mut token = self.make_double(Token::Ellipsis);
+                    self.advance();
+                    token.end.advance(self.current());
+                    tokens.push(token);
+                },
                 '.' => tokens.push(self.make_single(Token::Dot)),
+                '\n' | '\0' if interpolation => return Err(LexerError::UnexpectedChar(self.make_single(current))),
                 '\n' => {
                     let start = self.pos.clone();
                     let mut end = self.pos.clone();
@@ -147,15 +233,19 @@ impl Lexer {
                 '\t' => tokens.push(self.make_single(Token::Tab)),
                 '=' => {
                     if self.peek(1) == '>' {
-                        let start = self.pos.clone();
-                        self.advance();
-                        let mut end = self.pos.clone();
-                        end.advance(self.current());
-                        tokens.push(Positioned::new(Token::RightDoubleArrow, start, end));
+                        tokens.push(self.make_double(Token::RightDoubleArrow));
+                    } else if self.peek(1) == '=' {
+                        tokens.push(self.make_double(Token::DoubleEqual));
                     } else {
                         tokens.push(self.make_single(Token::Equal));
                     }
                 }
+                '!' if self.peek(1) == '=' => tokens.push(self.make_double(Token::NotEqual)),
+                '!' => tokens.push(self.make_single(Token::Exclamation)),
+                '<' if self.peek(1) == '=' => tokens.push(self.make_double(Token::LessEqual)),
+                '<' => tokens.push(self.make_single(Token::Less)),
+                '>' if self.peek(1) == '=' => tokens.push(self.make_double(Token::GreaterEqual)),
+                '>' => tokens.push(self.make_single(Token::Greater)),
                 '#' => {
                     while current != '\n' && current != '\0' {
                         self.advance();
@@ -168,7 +258,7 @@ impl Lexer {
             self.advance();
         }      
 
-        Ok(tokens)
+        Ok(())
     }
 
     pub fn take(self) -> SourceFile {
@@ -6,11 +6,27 @@ pub enum Keyword {
     Const,
     Var,
     Return,
+    Defer,
     Extern,
     Include,
+    Import,
     Class,
+    Struct,
     This,
-    New
+    New,
+    Null,
+    None,
+    If,
+    Else,
+    Let,
+    As,
+    Unsafe,
+    Type,
+    Static,
+    Pub,
+    Drop,
+    True,
+    False
 }
 
 impl Keyword {
@@ -21,11 +37,27 @@ impl Keyword {
             "const" => Some(Keyword::Const),
             "var" => Some(Keyword::Var),
             "return" => Some(Keyword::Return),
+            "defer" => Some(Keyword::Defer),
             "extern" => Some(Keyword::Extern),
             "include" => Some(Keyword::Include),
+            "import" => Some(Keyword::Import),
             "class" => Some(Keyword::Class),
+            "struct" => Some(Keyword::Struct),
             "self" => Some(Keyword::This),
             "new" => Some(Keyword::New),
+            "null" => Some(Keyword::Null),
+            "none" => Some(Keyword::None),
+            "if" => Some(Keyword::If),
+            "else" => Some(Keyword::Else),
+            "let" => Some(Keyword::Let),
+            "as" => Some(Keyword::As),
+            "unsafe" => Some(Keyword::Unsafe),
+            "type" => Some(Keyword::Type),
+            "static" => Some(Keyword::Static),
+            "pub" => Some(Keyword::Pub),
+            "drop" => Some(Keyword::Drop),
+            "true" => Some(Keyword::True),
+            "false" => Some(Keyword::False),
             _ => None
         }
     }
@@ -40,11 +72,27 @@ impl Display for Keyword {
             Keyword::Const => write!(f, "const"),
             Keyword::Var => write!(f, "var"),
             Keyword::Return => write!(f, "return"),
+            Keyword::Defer => write!(f, "defer"),
             Keyword::Extern => write!(f, "extern"),
             Keyword::Include => write!(f, "include"),
+            Keyword::Import => write!(f, "import"),
             Keyword::Class => write!(f, "class"),
+            Keyword::Struct => write!(f, "struct"),
             Keyword::This => write!(f, "self"),
             Keyword::New => write!(f, "new"),
+            Keyword::Null => write!(f, "null"),
+            Keyword::None => write!(f, "none"),
+            Keyword::If => write!(f, "if"),
+            Keyword::Else => write!(f, "else"),
+            Keyword::Let => write!(f, "let"),
+            Keyword::As => write!(f, "as"),
+            Keyword::Unsafe => write!(f, "unsafe"),
+            Keyword::Type => write!(f, "type"),
+            Keyword::Static => write!(f, "static"),
+            Keyword::Pub => write!(f, "pub"),
+            Keyword::Drop => write!(f, "drop"),
+            Keyword::True => write!(f, "true"),
+            Keyword::False => write!(f, "false"),
         }
     }
 
@@ -54,18 +102,39 @@ impl Display for Keyword {
 pub enum Token {
     Decimal(String),
     String(String),
+    // Strings with embedded expressions: start, then the parts (String or {tokens}), then end
+    InterpolationStart,
+    InterpolationEnd,
     Identifier(String),
     Keyword(Keyword),
     Plus,
     Dash,
     Star,
     Slash,
+    Ampersand,
+    DoubleAmpersand,
+    DoublePipe,
+    Exclamation,
+    At,
     Equal,
+    DoubleEqual,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
     Colon,
     Comma,
     Dot,
+    Ellipsis,
+    SemiColon,
+    Question,
     LeftParenthesis,
     RightParenthesis,
+    LeftBracket,
+    RightBracket,
+    LeftBrace,
+    RightBrace,
     RightDoubleArrow,
     NewLine,
     Tab
@@ -77,18 +146,38 @@ impl Display for Token {
         match self {
             Token::Decimal(val) => write!(f, "{}", val),
             Token::String(val) => write!(f, "\"{}\"", val),
+            Token::InterpolationStart => write!(f, "InterpolationStart"),
+            Token::InterpolationEnd => write!(f, "InterpolationEnd"),
             Token::Identifier(val) => write!(f, "{}", val),
             Token::Keyword(keyword) => write!(f, "Keyword({})", keyword),
             Token::Plus => write!(f, "+"),
             Token::Dash => write!(f, "-"),
             Token::Star => write!(f, "*"),
             Token::Slash => write!(f, "/"),
+            Token::Ampersand => write!(f, "&"),
+            Token::DoubleAmpersand => write!(f, "&&"),
+            Token::DoublePipe => write!(f, "||"),
+            Token::Exclamation => write!(f, "!"),
+            Token::At => write!(f, "@"),
             Token::Equal => write!(f, "="),
+            Token::DoubleEqual => write!(f, "=="),
+            Token::NotEqual => write!(f, "!="),
+            Token::Less => write!(f, "<"),
+            Token::LessEqual => write!(f, "<="),
+            Token::Greater => write!(f, ">"),
+            Token::GreaterEqual => write!(f, ">="),
             Token::Colon => write!(f, ":"),
             Token::Comma => write!(f, ","),
             Token::Dot => write!(f, "."),
+            Token::Ellipsis => write!(f, "..."),
+            Token::SemiColon => write!(f, ";"),
+            Token::Question => write!(f, "?"),
             Token::LeftParenthesis => write!(f, "("),
             Token::RightParenthesis => write!(f, ")"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::LeftBrace => write!(f, "{{"),
+            Token::RightBrace => write!(f, "}}"),
             Token::RightDoubleArrow => write!(f, "=>"),
             Token::NewLine => write!(f, "NewLine"),
             Token::Tab => write!(f, "Tab"),
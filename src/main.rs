@@ -1,4 +1,6 @@
-use crate::{util::file::SourceFile, lexer::lexer::Lexer, parser::parser::Parser, translator::translator::Translator, checker::checker::Checker};
+use std::{collections::BTreeMap, sync::{Arc, Mutex}};
+
+use crate::{util::{file::SourceFile, position::Positioned}, lexer::lexer::Lexer, parser::{parser::Parser, node::Node}, translator::translator::Translator, checker::checker::Checker};
 
 pub mod util;
 pub mod lexer;
@@ -8,19 +10,81 @@ pub mod checker;
 
 // TODO: Create full project with file structure and CMakeList.txt
 // FIXME: Fix issue with TAB after new line in function body
-// FIXME: Add TAB count for function
 // FIXME: Consider issue with includes in header file (because of pushing at the beginning) [do the same thing as the checker (post processing)]
 // TODO: Add errors to transpile_project()
 // TODO: Checker errors
-// TODO: Add Destructor
-// TODO: rename functions and fields depending on the class
 // TODO: transform non-c-type to pointers [checker]
 // FIXME: Change c_byte to c_char
 
-fn transpile_project(folder: &str) {    
+// Lexes and parses one file, errors are printed
+fn parse_file(name: String, path: std::path::PathBuf) -> Option<(SourceFile, Vec<Positioned<Node>>)> {
+    let src = std::fs::read_to_string(path).unwrap();
+
+    let mut src = SourceFile::new(name, src);
+
+    println!("\n\n--- Lexer ---");
+    let mut lexer = Lexer::new(src);
+
+    let tokens = match lexer.tokenize() {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            src = lexer.take();
+            err.print_error(src);
+            return None;
+        },
+    };
+
+    src = lexer.take();
+    for token in tokens.iter() {
+        println!("{}\n", token.data);
+    }
+
+    println!("\n\n--- Parser ---");
+    let mut parser = Parser::new(src, tokens);
+    let ast = match parser.parse() {
+        Ok(ast) => ast,
+        Err(err) => {
+            src = parser.take();
+            err.print_error(src);
+            return None;
+        },
+    };
+
+    src = parser.take();
+    for node in ast.iter() {
+        println!("{}\n", node.data);
+        // println!("{}\n", node.arrow_message(&src.src));
+    }
+
+    Some((src, ast))
+}
+
+// Imported modules come first (depth first, by name so that the order is deterministic)
+fn visit_module(name: &String, imports: &BTreeMap<String, Vec<Positioned<String>>>, order: &mut Vec<String>, stack: &mut Vec<String>) {
+    if order.contains(name) {
+        return;
+    }
+    if let Some(index) = stack.iter().position(|x| x == name) {
+        panic!("Import cycle detected: {} -> {}", stack[index..].join(" -> "), name);
+    }
+
+    stack.push(name.clone());
+    for import in imports[name].iter() {
+        if !imports.contains_key(&import.data) {
+            panic!("Module '{}' imported by '{}' at {} not found!", import.data, name, import.start);
+        }
+        visit_module(&import.data, imports, order, stack);
+    }
+    stack.pop();
+
+    order.push(name.clone());
+}
+
+fn transpile_project(folder: &str, bounds_checks: bool) {    
     std::fs::remove_dir_all("./out").unwrap();
     std::fs::create_dir("./out").unwrap();
 
+    let mut files = Vec::new();
     for file in std::fs::read_dir(folder).unwrap() {
         let file = file.unwrap();
 
@@ -30,78 +94,79 @@ fn transpile_project(folder: &str) {
                 continue;
             }
             name = (&name[0..(name.len() - 5)]).to_string();
-            
-            let src = std::fs::read_to_string(file.path()).unwrap();
-
-            let mut src = SourceFile::new(name, src);
-
-            println!("\n\n--- Lexer ---");
-            let mut lexer = Lexer::new(src);
-
-            let tokens = match lexer.tokenize() {
-                Ok(tokens) => tokens,
-                Err(err) => {
-                    src = lexer.take();
-                    err.print_error(src);
-                    return;
-                },
-            };
-        
-            src = lexer.take();
-            for token in tokens.iter() {
-                println!("{}\n", token.data);
-            }
-        
-            println!("\n\n--- Parser ---");
-            let mut parser = Parser::new(src, tokens);
-            let mut ast = match parser.parse() {
-                Ok(ast) => ast,
-                Err(err) => {
-                    src = parser.take();
-                    err.print_error(src);
-                    return;
-                },
-            };
-        
-            src = parser.take();
-            for node in ast.iter() {
-                println!("{}\n", node.data);
-                // println!("{}\n", node.arrow_message(&src.src));
-            }
+            files.push((name, file.path()));
+        }
+    }
+    files.sort();
 
-            println!("\n\n--- Checker ---");
-            let mut checker = Checker::new(src, ast);
-            ast = checker.check();
-            src = checker.take();
+    // Parse every module
+    let mut modules = BTreeMap::new();
+    for (name, path) in files {
+        let Some(module) = parse_file(name.clone(), path) else {
+            return;
+        };
+        modules.insert(name, module);
+    }
+
+    // Compile order
+    let mut imports = BTreeMap::new();
+    for (name, (_, ast)) in modules.iter() {
+        let modules: Vec<Positioned<String>> = ast.iter().filter_map(|node| match &node.data {
+            Node::Import { module, .. } => Some(module.clone()),
+            _ => None
+        }).collect();
+        imports.insert(name.clone(), modules);
+    }
+    let mut order = Vec::new();
+    for name in imports.keys() {
+        visit_module(name, &imports, &mut order, &mut Vec::new());
+    }
+
+    let mut symbols = Vec::new();
+    let mut classes = Vec::new();
+    let mut structs = Vec::new();
+    for name in order {
+        let (mut src, mut ast) = modules.remove(&name).unwrap();
+
+        println!("\n\n--- Checker ---");
+        let mut checker = Checker::new(src, ast, symbols.clone());
+        ast = checker.check();
+        let module = checker.module();
+        src = checker.take();
 
-            
-            for node in ast.iter() {
-                println!("{}\n", node.data);
-                // println!("{}\n", node.arrow_message(&src.src));
-            }
-        
-            println!("\n\n--- Translator ---");
-            let mut translator = Translator::new(src, ast);
-            let project = translator.translate();
-            src = translator.take();
         
-            println!("\n\n");
-            for file in project.files.iter() {
-                if !file.header.is_empty() {
-                    std::fs::write(format!("./out/{}.h", file.name), &file.header).unwrap();
-                    // println!("{}.h", file.name);
-                    // println!("{}\n", file.header);
-                }
-                if !file.src.is_empty() {
-                    std::fs::write(format!("./out/{}.c", file.name), &file.src).unwrap();
-                    // println!("{}.c", file.name);
-                    // println!("{}\n", file.src);
-                }
+        for node in ast.iter() {
+            println!("{}\n", node.data);
+            // println!("{}\n", node.arrow_message(&src.src));
+        }
+
+        // Modules can use the classes of the modules they import
+        classes.append(&mut module.instance_names());
+        structs.append(&mut module.struct_names());
+        symbols.push(Arc::new(Mutex::new(module)));
+    
+        println!("\n\n--- Translator ---");
+        let mut translator = Translator::new(src, ast, classes.clone(), structs.clone(), bounds_checks);
+        let project = translator.translate();
+        _ = translator.take();
+    
+        println!("\n\n");
+        for file in project.files.iter() {
+            if !file.header.is_empty() {
+                std::fs::write(format!("./out/{}.h", file.name), &file.header).unwrap();
+                // println!("{}.h", file.name);
+                // println!("{}\n", file.header);
+            }
+            if !file.src.is_empty() {
+                std::fs::write(format!("./out/{}.c", file.name), &file.src).unwrap();
+                // println!("{}.c", file.name);
+                // println!("{}\n", file.src);
             }
         }
     }
 } 
 
 fn main() {
-    transpile_project("./res");
+    let bounds_checks = std::env::args().any(|arg| arg == "--bounds-checks");
+    transpile_project("./res", bounds_checks);
 }
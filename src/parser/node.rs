@@ -10,28 +10,123 @@ pub enum Node {
         op: Positioned<Operator>,
         rhs: Box<Positioned<Node>>
     },
+    UnaryOperation {
+        op: Positioned<UnaryOperator>,
+        value: Box<Positioned<Node>>
+    },
     VariableDefinition {
         var_type: Positioned<VarType>,
         name: Positioned<String>,
-        data_type: Option<Positioned<String>>,
-        value: Option<Box<Positioned<Node>>>
+        data_type: Option<Positioned<TypeExpr>>,
+        value: Option<Box<Positioned<Node>>>,
+        public: bool,
+        attributes: Vec<Attribute>
     },
     FunctionDefinition {
         name: Positioned<String>,
-        return_type: Option<Positioned<String>>,
+        return_type: Option<Positioned<TypeExpr>>,
         params: Vec<FunctionDefinitionParameter>,
         body: Option<Vec<Positioned<Node>>>,
         constructor: bool,
+        constant: bool, // Evaluated at compile time with constant arguments
+        variadic: bool,
+        public: bool,
+        attributes: Vec<Attribute>
     }, 
     Return(Box<Positioned<Node>>),
+    Defer(Box<Positioned<Node>>),
+    If {
+        condition: Box<Positioned<Node>>, // A variable definition for "if let"
+        body: Vec<Positioned<Node>>,
+        else_body: Option<Vec<Positioned<Node>>>
+    },
     FunctionCall {
         name: Positioned<String>,
         params: Vec<FunctionCallParameter>,
     },
     Include (Positioned<String>),
+    TypeAlias {
+        name: Positioned<String>,
+        data_type: Positioned<TypeExpr>,
+        public: bool
+    },
+    Import {
+        module: Positioned<String>,
+        names: Option<Vec<Positioned<String>>>
+    },
     ClassDefinition {
         name: Positioned<String>,
-        body: Vec<Positioned<Node>>
+        body: Vec<Positioned<Node>>,
+        public: bool,
+        attributes: Vec<Attribute>
+    },
+    // Class members without instance
+    Static(Box<Positioned<Node>>),
+    // Types and constants defined by C headers
+    ExternClass {
+        name: Positioned<String>,
+        public: bool
+    },
+    ExternStruct {
+        name: Positioned<String>,
+        fields: Vec<Positioned<Node>>,
+        public: bool,
+        attributes: Vec<Attribute>
+    },
+    ExternConstant {
+        name: Positioned<String>,
+        data_type: Positioned<TypeExpr>,
+        public: bool
+    },
+    Array {
+        data_type: Option<Positioned<TypeExpr>>,
+        values: Vec<Positioned<Node>>
+    },
+    Tuple {
+        data_type: Option<Positioned<TypeExpr>>,
+        values: Vec<Positioned<Node>>
+    },
+    // var (a, b) = tuple
+    Destructuring {
+        var_type: Positioned<VarType>,
+        names: Vec<Positioned<String>>,
+        data_type: Option<Positioned<TypeExpr>>,
+        value: Box<Positioned<Node>>
+    },
+    Index {
+        value: Box<Positioned<Node>>,
+        index: Box<Positioned<Node>>,
+        length: Option<Box<Positioned<Node>>>
+    },
+    Slice {
+        data_type: Positioned<TypeExpr>,
+        value: Box<Positioned<Node>>,
+        length: Box<Positioned<Node>>
+    },
+    Lambda {
+        params: Vec<FunctionDefinitionParameter>,
+        return_type: Option<Positioned<TypeExpr>>,
+        body: Box<Positioned<Node>>,
+        captures: Vec<FunctionDefinitionParameter>
+    },
+    ClosureCall {
+        value: Box<Positioned<Node>>,
+        params: Vec<FunctionCallParameter>
+    },
+    Try(Box<Positioned<Node>>),
+    Cast {
+        value: Box<Positioned<Node>>,
+        data_type: Positioned<TypeExpr>
+    },
+    Unsafe(Vec<Positioned<Node>>),
+    // Lines of C code (strings), "{name}" references a variable
+    InlineC(Vec<Positioned<Node>>),
+    Interpolation(Vec<InterpolationPart>),
+    // Only generated by the checker (ok / err)
+    Result {
+        data_type: Option<Positioned<TypeExpr>>,
+        ok: bool,
+        value: Option<Box<Positioned<Node>>>
     }
 }
 
@@ -48,11 +143,34 @@ impl Display for Node {
                     Operator::Multiply => write!(f, " * ")?,
                     Operator::Divide => write!(f, " / ")?,
                     Operator::MemberAccess => write!(f, ".")?,
+                    Operator::FieldAccess => write!(f, ".")?,
+                    Operator::Equal => write!(f, " == ")?,
+                    Operator::NotEqual => write!(f, " != ")?,
+                    Operator::Less => write!(f, " < ")?,
+                    Operator::LessEqual => write!(f, " <= ")?,
+                    Operator::Greater => write!(f, " > ")?,
+                    Operator::GreaterEqual => write!(f, " >= ")?,
+                    Operator::And => write!(f, " && ")?,
+                    Operator::Or => write!(f, " || ")?,
                     Operator::Assignment => write!(f, " = ")?,
                 }
                 write!(f, "{})", rhs.data)?;
             },
-            Node::VariableDefinition { var_type, name, data_type, value } => {
+            Node::UnaryOperation { op, value } => {
+                match op.data {
+                    UnaryOperator::AddressOf => write!(f, "&")?,
+                    UnaryOperator::Dereference => write!(f, "*")?,
+                    UnaryOperator::Not => write!(f, "!")?,
+                }
+                write!(f, "{}", value.data)?;
+            },
+            Node::VariableDefinition { var_type, name, data_type, value, public, attributes } => {
+                for attribute in attributes.iter() {
+                    write!(f, "{} ", attribute)?;
+                }
+                if *public {
+                    write!(f, "pub ")?;
+                }
                 match var_type.data {
                     VarType::Constant => write!(f, "const ")?,
                     VarType::Variable => write!(f, "var ")?,
@@ -68,10 +186,19 @@ impl Display for Node {
                     write!(f, " = {}", value.data)?;
                 }
             },
-            Node::FunctionDefinition { name, return_type, params, body, constructor } => {
+            Node::FunctionDefinition { name, return_type, params, body, constructor, constant, variadic, public, attributes } => {
+                for attribute in attributes.iter() {
+                    write!(f, "{} ", attribute)?;
+                }
+                if *public {
+                    write!(f, "pub ")?;
+                }
                 if body.is_none() {
                     write!(f, "extern ")?;
                 }
+                if *constant {
+                    write!(f, "const ")?;
+                }
 
                 if *constructor {
                     write!(f, "new ")?;
@@ -91,6 +218,9 @@ impl Display for Node {
 
                     i += 1;
                 }
+                if *variadic {
+                    write!(f, "{}...", if i != 0 { ", " } else { "" })?;
+                }
                 write!(f, ")")?;
 
                 // Return type
@@ -109,6 +239,26 @@ impl Display for Node {
                 }
             },
             Node::Return(node) => write!(f, "return {}", node.data)?,
+            Node::Defer(node) => write!(f, "defer {}", node.data)?,
+            Node::If { condition, body, else_body } => {
+                match &condition.data {
+                    Node::VariableDefinition { name, value: Some(value), .. } => write!(f, "if let {} = {} =>", name.data, value.data)?,
+                    _ => write!(f, "if {} =>", condition.data)?
+                }
+                for node in body.iter() {
+                    for line in node.data.to_string().lines() {
+                        write!(f, "\n\t{}", line)?;
+                    }
+                }
+                if let Some(else_body) = else_body {
+                    write!(f, "\nelse =>")?;
+                    for node in else_body.iter() {
+                        for line in node.data.to_string().lines() {
+                            write!(f, "\n\t{}", line)?;
+                        }
+                    }
+                }
+            },
             Node::FunctionCall { name, params } => {
                 write!(f, "{}(", name.data)?;
                 let mut i = 0;
@@ -122,7 +272,32 @@ impl Display for Node {
                 write!(f, ")")?;
             },
             Node::Include(path) => write!(f, "include \"{}\"", path.data)?,
-            Node::ClassDefinition { name, body } => {
+            Node::TypeAlias { name, data_type, public } => {
+                if *public {
+                    write!(f, "pub ")?;
+                }
+                write!(f, "type {} = {}", name.data, data_type.data)?;
+            },
+            Node::Import { module, names } => {
+                write!(f, "import {}", module.data)?;
+                if let Some(names) = names {
+                    write!(f, ".{{")?;
+                    for (i, name) in names.iter().enumerate() {
+                        if i != 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", name.data)?;
+                    }
+                    write!(f, "}}")?;
+                }
+            },
+            Node::ClassDefinition { name, body, public, attributes } => {
+                for attribute in attributes.iter() {
+                    write!(f, "{} ", attribute)?;
+                }
+                if *public {
+                    write!(f, "pub ")?;
+                }
                 write!(f, "class {}", name.data)?;
 
                 for node in body.iter() {
@@ -132,28 +307,174 @@ impl Display for Node {
                     }
                 }
             },
+            Node::Static(node) => {
+                let str = node.data.to_string();
+                match str.strip_prefix("pub ") {
+                    Some(str) => write!(f, "pub static {}", str)?,
+                    None => write!(f, "static {}", str)?
+                }
+            },
+            Node::ExternClass { name, public } => {
+                if *public {
+                    write!(f, "pub ")?;
+                }
+                write!(f, "extern class {}", name.data)?;
+            },
+            Node::ExternStruct { name, fields, public, attributes } => {
+                for attribute in attributes.iter() {
+                    write!(f, "{} ", attribute)?;
+                }
+                if *public {
+                    write!(f, "pub ")?;
+                }
+                write!(f, "extern struct {}", name.data)?;
+
+                for field in fields.iter() {
+                    write!(f, "\n\t{}", field.data)?;
+                }
+            },
+            Node::ExternConstant { name, data_type, public } => {
+                if *public {
+                    write!(f, "pub ")?;
+                }
+                write!(f, "extern const {}: {}", name.data, data_type.data)?;
+            },
+            Node::Array { data_type: _, values } => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value.data)?;
+                }
+                write!(f, "]")?;
+            },
+            Node::Tuple { data_type: _, values } => {
+                write!(f, "(")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value.data)?;
+                }
+                write!(f, ")")?;
+            },
+            Node::Destructuring { var_type, names, data_type, value } => {
+                match var_type.data {
+                    VarType::Constant => write!(f, "const (")?,
+                    VarType::Variable => write!(f, "var (")?,
+                }
+                for (i, name) in names.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", name.data)?;
+                }
+                write!(f, ")")?;
+                if let Some(data_type) = data_type {
+                    write!(f, ": {}", data_type.data)?;
+                }
+                write!(f, " = {}", value.data)?;
+            },
+            Node::Index { value, index, length: _ } => write!(f, "{}[{}]", value.data, index.data)?,
+            Node::Slice { data_type: _, value, length } => write!(f, "{}[0..{}]", value.data, length.data)?,
+            Node::Lambda { params, return_type, body, captures: _ } => {
+                write!(f, "fn(")?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", param.name.data, param.data_type.data)?;
+                }
+                write!(f, ")")?;
+                if let Some(return_type) = return_type {
+                    write!(f, ": {}", return_type.data)?;
+                }
+                write!(f, " => {}", body.data)?;
+            },
+            Node::ClosureCall { value, params } => {
+                write!(f, "{}(", value.data)?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param.value.data)?;
+                }
+                write!(f, ")")?;
+            },
+            Node::Try(value) => write!(f, "{}?", value.data)?,
+            Node::Cast { value, data_type } => write!(f, "({} as {})", value.data, data_type.data)?,
+            Node::Unsafe(body) => {
+                write!(f, "unsafe =>")?;
+                for node in body.iter() {
+                    for line in node.data.to_string().lines() {
+                        write!(f, "\n\t{}", line)?;
+                    }
+                }
+            },
+            Node::InlineC(lines) => {
+                if let [line] = lines.as_slice() {
+                    write!(f, "c {}", line.data)?;
+                } else {
+                    write!(f, "c =>")?;
+                    for line in lines.iter() {
+                        write!(f, "\n\t{}", line.data)?;
+                    }
+                }
+            },
+            Node::Interpolation(parts) => {
+                write!(f, "\"")?;
+                for part in parts.iter() {
+                    match part {
+                        InterpolationPart::Text(text) => write!(f, "{}", text.replace('{', "{{").replace('}', "}}"))?,
+                        InterpolationPart::Value(value, _) => write!(f, "{{{}}}", value.data)?,
+                    }
+                }
+                write!(f, "\"")?;
+            },
+            Node::Result { data_type: _, ok, value } => {
+                write!(f, "{}(", if *ok { "ok" } else { "err" })?;
+                if let Some(value) = value {
+                    write!(f, "{}", value.data)?;
+                }
+                write!(f, ")")?;
+            },
         }
         Ok(())
     }
 
 }
 
+#[derive(Clone)]
+pub enum InterpolationPart {
+    Text(String),
+    Value(Box<Positioned<Node>>, Option<Positioned<TypeExpr>>) // The type is set by the checker
+}
+
 #[derive(Clone)]
 pub enum ValueNode {
     Decimal(String),
+    Float(String),
     String(String),
+    Bool(bool),
     VariableCall(String),
     This,
+    Null,
+    None,
 }
 
 impl Display for ValueNode {
 
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ValueNode::Decimal(val) => write!(f, "{}", val),
+            ValueNode::Decimal(val) |
+            ValueNode::Float(val) => write!(f, "{}", val),
             ValueNode::String(val) => write!(f, "\"{}\"", val),
+            ValueNode::Bool(val) => write!(f, "{}", val),
             ValueNode::VariableCall(name) => write!(f, "{}", name),
             ValueNode::This => write!(f, "self"),
+            ValueNode::Null => write!(f, "null"),
+            ValueNode::None => write!(f, "none"),
         }
     }
 
@@ -166,9 +487,25 @@ pub enum Operator {
     Multiply,
     Divide,
     MemberAccess,
+    FieldAccess,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
     Assignment
 }
 
+#[derive(Clone)]
+pub enum UnaryOperator {
+    AddressOf,
+    Dereference,
+    Not
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum VarType {
     Constant,
@@ -178,10 +515,126 @@ pub enum VarType {
 #[derive(Clone, Debug)]
 pub struct FunctionDefinitionParameter {
     pub name: Positioned<String>,
-    pub data_type: Positioned<String>
+    pub data_type: Positioned<TypeExpr>
+}
+
+// @name or @name("value")
+#[derive(Clone)]
+pub struct Attribute {
+    pub name: Positioned<String>,
+    pub value: Option<Positioned<String>>
+}
+
+impl Display for Attribute {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}", self.name.data)?;
+        if let Some(value) = &self.value {
+            write!(f, "(\"{}\")", value.data)?;
+        }
+        Ok(())
+    }
+
 }
 
 #[derive(Clone)]
 pub struct FunctionCallParameter {
     pub value: Positioned<Node>,
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Debug)]
+pub enum TypeExpr {
+    Named(String),
+    Generic {
+        name: String,
+        params: Vec<Positioned<TypeExpr>>
+    },
+    Pointer {
+        constant: bool,
+        inner: Box<Positioned<TypeExpr>>
+    },
+    Array {
+        inner: Box<Positioned<TypeExpr>>,
+        size: Option<Positioned<String>>
+    },
+    Optional(Box<Positioned<TypeExpr>>),
+    Tuple(Vec<Positioned<TypeExpr>>),
+    Function {
+        params: Vec<Positioned<TypeExpr>>,
+        return_type: Option<Box<Positioned<TypeExpr>>>
+    },
+    // Capturing lambdas (closure fn(...): type)
+    Closure {
+        params: Vec<Positioned<TypeExpr>>,
+        return_type: Box<Positioned<TypeExpr>>
+    }
+}
+
+impl Display for TypeExpr {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeExpr::Named(name) => write!(f, "{}", name)?,
+            TypeExpr::Generic { name, params } => {
+                write!(f, "{}[", name)?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param.data)?;
+                }
+                write!(f, "]")?;
+            },
+            TypeExpr::Pointer { constant, inner } => {
+                write!(f, "*")?;
+                if *constant {
+                    write!(f, "const ")?;
+                }
+                write!(f, "{}", inner.data)?;
+            },
+            TypeExpr::Array { inner, size } => {
+                if let Some(size) = size {
+                    write!(f, "[{}; {}]", inner.data, size.data)?;
+                } else {
+                    write!(f, "[{}]", inner.data)?;
+                }
+            },
+            TypeExpr::Optional(inner) => write!(f, "{}?", inner.data)?,
+            TypeExpr::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element.data)?;
+                }
+                write!(f, ")")?;
+            },
+            TypeExpr::Function { params, return_type } => {
+                write!(f, "fn(")?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param.data)?;
+                }
+                write!(f, ")")?;
+                if let Some(return_type) = return_type {
+                    write!(f, ": {}", return_type.data)?;
+                }
+            },
+            TypeExpr::Closure { params, return_type } => {
+                write!(f, "closure fn(")?;
+                for (i, param) in params.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param.data)?;
+                }
+                write!(f, "): {}", return_type.data)?;
+            },
+        }
+        Ok(())
+    }
+
+}
@@ -1,9 +1,10 @@
-use crate::{util::{file::SourceFile, position::{Positioned, Position}}, lexer::token::{Token, Keyword}, parser::{error::ParserError, node::{Node, ValueNode, Operator, FunctionCallParameter, VarType, FunctionDefinitionParameter}}};
+use crate::{util::{file::SourceFile, position::{Positioned, Position}}, lexer::token::{Token, Keyword}, parser::{error::ParserError, node::{Node, ValueNode, InterpolationPart, Operator, UnaryOperator, FunctionCallParameter, VarType, FunctionDefinitionParameter, TypeExpr, Attribute}}};
 
 pub struct Parser {
     src: SourceFile,
     tokens: Vec<Positioned<Token>>,
-    index: usize
+    index: usize,
+    indent: usize
 }
 
 impl Parser {
@@ -12,7 +13,8 @@ impl Parser {
         Self {
             src,
             tokens,
-            index: 0
+            index: 0,
+            indent: 0
         }
     }
 
@@ -89,10 +91,18 @@ impl Parser {
         let current = self.current();
         if let Some(current) = &current {
             match &current.data {
+                Token::Decimal(value) if value.contains('.') => Ok(current.convert(Node::Value(ValueNode::Float(value.clone())))),
                 Token::Decimal(value) => Ok(current.convert(Node::Value(ValueNode::Decimal(value.clone())))),
                 Token::String(value) => Ok(current.convert(Node::Value(ValueNode::String(value.clone())))),
+                Token::InterpolationStart => self.parse_interpolation(current.start.clone()),
                 Token::Identifier(id) => self.handle_identifier(current.convert(id.clone())),
                 Token::Keyword(Keyword::This) => Ok(current.convert(Node::Value(ValueNode::This))),
+                Token::Keyword(Keyword::Null) => Ok(current.convert(Node::Value(ValueNode::Null))),
+                Token::Keyword(Keyword::None) => Ok(current.convert(Node::Value(ValueNode::None))),
+                Token::Keyword(Keyword::True) => Ok(current.convert(Node::Value(ValueNode::Bool(true)))),
+                Token::Keyword(Keyword::False) => Ok(current.convert(Node::Value(ValueNode::Bool(false)))),
+                Token::LeftBracket => self.parse_array(current.start.clone()),
+                Token::LeftParenthesis => self.parse_parenthesis(current.start.clone()),
                 _ => Err(ParserError::UnexpectedToken(current.clone(), Some("expr0".to_string())))
             }
         } else {
@@ -130,6 +140,80 @@ impl Parser {
         }, start, end));
     }
 
+    // The InterpolationEnd token becomes the current token
+    fn parse_interpolation(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+
+        let mut parts = Vec::new();
+        let mut current = self.expect_current(None, Some("\"".to_string()))?;
+        while current.data != Token::InterpolationEnd {
+            match &current.data {
+                Token::String(text) => parts.push(InterpolationPart::Text(text.clone())),
+                Token::LeftBrace => {
+                    self.advance();
+                    let value = self.parse_expr()?;
+                    self.expect_current(Some(Token::RightBrace), Some("}".to_string()))?;
+                    parts.push(InterpolationPart::Value(Box::new(value), None));
+                },
+                _ => return Err(ParserError::UnexpectedToken(current, Some("\"".to_string())))
+            }
+            self.advance();
+            current = self.expect_current(None, Some("\"".to_string()))?;
+        }
+
+        Ok(Positioned::new(Node::Interpolation(parts), start, current.end))
+    }
+
+    // (value) or a tuple (value, value, ...)
+    fn parse_parenthesis(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let value = self.parse_expr()?;
+        let mut current = self.expect_current(None, Some(")".to_string()))?;
+        if current.data != Token::Comma {
+            self.expect_current(Some(Token::RightParenthesis), Some(")".to_string()))?;
+            return Ok(Positioned::new(value.data, start, current.end));
+        }
+
+        let mut values = vec![value];
+        while current.data != Token::RightParenthesis {
+            self.expect_current(Some(Token::Comma), Some(",".to_string()))?;
+            self.advance();
+            values.push(self.parse_expr()?);
+            current = self.expect_current(None, Some(")".to_string()))?;
+        }
+        Ok(Positioned::new(Node::Tuple { data_type: None, values }, start, current.end))
+    }
+
+    fn parse_array(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let mut current = self.expect_current(None, Some("]".to_string()))?;
+
+        let mut values = Vec::new();
+        while current.data != Token::RightBracket {
+            if !values.is_empty() {
+                self.expect_current(Some(Token::Comma), Some(",".to_string()))?;
+                self.advance();
+            }
+            values.push(self.parse_expr()?);
+            current = self.expect_current(None, Some("]".to_string()))?;
+        }
+
+        Ok(Positioned::new(Node::Array { data_type: None, values }, start, current.end))
+    }
+
+    fn parse_index(&mut self, value: Positioned<Node>) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let index = self.parse_expr()?;
+        let current = self.expect_current(Some(Token::RightBracket), Some("]".to_string()))?;
+
+        let start = value.start.clone();
+        Ok(Positioned::new(Node::Index { 
+            value: Box::new(value), 
+            index: Box::new(index), 
+            length: None 
+        }, start, current.end))
+    }
+
     fn parse_expr1(&mut self) -> Result<Positioned<Node>, ParserError> {
         let mut left = self.parse_expr0()?;
 
@@ -141,6 +225,15 @@ impl Parser {
 
             let op = match current.data {
                 Token::Dot => current.convert(Operator::MemberAccess),
+                Token::LeftBracket => {
+                    left = self.parse_index(left)?;
+                    continue;
+                }
+                Token::Question => {
+                    let start = left.start.clone();
+                    left = Positioned::new(Node::Try(Box::new(left)), start, current.end);
+                    continue;
+                }
                 _ => break
             };
             self.advance();
@@ -155,7 +248,74 @@ impl Parser {
     }
 
     fn parse_expr2(&mut self) -> Result<Positioned<Node>, ParserError> {
-        let mut left = self.parse_expr1()?;
+        let current = self.expect_current(None, Some("expr2".to_string()))?;
+
+        let op = match current.data {
+            Token::Ampersand => current.convert(UnaryOperator::AddressOf),
+            Token::Star => current.convert(UnaryOperator::Dereference),
+            Token::Exclamation => current.convert(UnaryOperator::Not),
+            Token::Keyword(Keyword::Fn) => return self.parse_lambda(current.start),
+            _ => return self.parse_expr1()
+        };
+        self.advance();
+
+        let value = self.parse_expr2()?;
+        let start = op.start.clone();
+        let end = value.end.clone();
+        Ok(Positioned::new(Node::UnaryOperation { op, value: Box::new(value) }, start, end))
+    }
+
+    fn parse_lambda(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+
+        // Get parameters
+        let (params, _, _) = self.parse_parameters(false)?;
+        self.advance();
+
+        // Get type
+        let mut return_type = None;
+        if self.expect_current(None, Some("=>".to_string()))?.data == Token::Colon {
+            self.advance();
+            return_type = Some(self.parse_type()?);
+            self.advance();
+        }
+
+        // Get body (everything up to the end of the expression)
+        self.expect_current(Some(Token::RightDoubleArrow), Some("=>".to_string()))?;
+        self.advance();
+        let body = self.parse_expr()?;
+        let end = body.end.clone();
+
+        Ok(Positioned::new(Node::Lambda { 
+            params, 
+            return_type, 
+            body: Box::new(body), 
+            captures: Vec::new() 
+        }, start, end))
+    }
+
+    // "value as Type" binds tighter than the binary operators
+    fn parse_cast(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut value = self.parse_expr2()?;
+
+        while let Some(current) = self.current() {
+            if current.data != Token::Keyword(Keyword::As) {
+                break;
+            }
+            self.advance();
+            let data_type = self.parse_type()?;
+            self.advance();
+
+            let start = value.start.clone();
+            let end = data_type.end.clone();
+            value = Positioned::new(Node::Cast { value: Box::new(value), data_type }, start, end);
+        }
+
+        Ok(value)
+    }
+
+    fn parse_expr3(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut left = self.parse_cast()?;
 
         loop {
             let Some(current) = self.current() else {
@@ -169,7 +329,7 @@ impl Parser {
             };
             self.advance();
 
-            let right = self.parse_expr1()?;
+            let right = self.parse_cast()?;
             let start = left.start.clone();
             let end = right.end.clone();
             left = Positioned::new(Node::BinaryOperation { lhs: Box::new(left), op, rhs: Box::new(right) }, start, end);
@@ -178,8 +338,8 @@ impl Parser {
         Ok(left)
     }
 
-    fn parse_expr3(&mut self) -> Result<Positioned<Node>, ParserError> {
-        let mut left = self.parse_expr2()?;
+    fn parse_expr4(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut left = self.parse_expr3()?;
 
         loop {
             let Some(current) = self.current() else {
@@ -193,7 +353,7 @@ impl Parser {
             };
             self.advance();
 
-            let right = self.parse_expr2()?;
+            let right = self.parse_expr3()?;
             let start = left.start.clone();
             let end = right.end.clone();
             left = Positioned::new(Node::BinaryOperation { lhs: Box::new(left), op, rhs: Box::new(right) }, start, end);
@@ -202,8 +362,82 @@ impl Parser {
         Ok(left)
     }
 
-    fn parse_expr4(&mut self) -> Result<Positioned<Node>, ParserError> {
-        let mut left = self.parse_expr3()?;
+    fn parse_expr5(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut left = self.parse_expr4()?;
+
+        loop {
+            let Some(current) = self.current() else {
+                break;
+            };
+
+            let op = match current.data {
+                Token::DoubleEqual => current.convert(Operator::Equal),
+                Token::NotEqual => current.convert(Operator::NotEqual),
+                Token::Less => current.convert(Operator::Less),
+                Token::LessEqual => current.convert(Operator::LessEqual),
+                Token::Greater => current.convert(Operator::Greater),
+                Token::GreaterEqual => current.convert(Operator::GreaterEqual),
+                _ => break
+            };
+            self.advance();
+
+            let right = self.parse_expr4()?;
+            let start = left.start.clone();
+            let end = right.end.clone();
+            left = Positioned::new(Node::BinaryOperation { lhs: Box::new(left), op, rhs: Box::new(right) }, start, end);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_expr6(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut left = self.parse_expr5()?;
+
+        loop {
+            let Some(current) = self.current() else {
+                break;
+            };
+
+            let op = match current.data {
+                Token::DoubleAmpersand => current.convert(Operator::And),
+                _ => break
+            };
+            self.advance();
+
+            let right = self.parse_expr5()?;
+            let start = left.start.clone();
+            let end = right.end.clone();
+            left = Positioned::new(Node::BinaryOperation { lhs: Box::new(left), op, rhs: Box::new(right) }, start, end);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_expr7(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut left = self.parse_expr6()?;
+
+        loop {
+            let Some(current) = self.current() else {
+                break;
+            };
+
+            let op = match current.data {
+                Token::DoublePipe => current.convert(Operator::Or),
+                _ => break
+            };
+            self.advance();
+
+            let right = self.parse_expr6()?;
+            let start = left.start.clone();
+            let end = right.end.clone();
+            left = Positioned::new(Node::BinaryOperation { lhs: Box::new(left), op, rhs: Box::new(right) }, start, end);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_expr8(&mut self) -> Result<Positioned<Node>, ParserError> {
+        let mut left = self.parse_expr7()?;
 
         loop {
             let Some(current) = self.current() else {
@@ -216,7 +450,7 @@ impl Parser {
             };
             self.advance();
 
-            let right = self.parse_expr3()?;
+            let right = self.parse_expr7()?;
             let start = left.start.clone();
             let end = right.end.clone();
             left = Positioned::new(Node::BinaryOperation { lhs: Box::new(left), op, rhs: Box::new(right) }, start, end);
@@ -226,11 +460,165 @@ impl Parser {
     }
 
     fn parse_expr(&mut self) -> Result<Positioned<Node>, ParserError> {
-        self.parse_expr4()
+        self.parse_expr8()
+    }
+
+    // Parses types separated by commas up to the closing token (which becomes the current token)
+    fn parse_type_list(&mut self, close: Token) -> Result<(Vec<Positioned<TypeExpr>>, Positioned<Token>), ParserError> {
+        let mut types = Vec::new();
+        let mut current = self.expect_current(None, Some(close.to_string()))?;
+        while current.data != close {
+            if !types.is_empty() {
+                self.expect_current(Some(Token::Comma), Some(",".to_string()))?;
+                self.advance();
+            }
+            types.push(self.parse_type()?);
+            self.advance();
+            current = self.expect_current(None, Some(close.to_string()))?;
+        }
+        Ok((types, current))
+    }
+
+    // (type, type, ...)
+    fn parse_tuple_type(&mut self, start: Position) -> Result<Positioned<TypeExpr>, ParserError> {
+        self.advance();
+        let (elements, current) = self.parse_type_list(Token::RightParenthesis)?;
+        if elements.len() < 2 {
+            return Err(ParserError::UnexpectedToken(current, Some(",".to_string())));
+        }
+        Ok(Positioned::new(TypeExpr::Tuple(elements), start, current.end))
+    }
+
+    fn parse_array_type(&mut self, start: Position) -> Result<Positioned<TypeExpr>, ParserError> {
+        self.advance();
+
+        // Element type
+        let inner = self.parse_type()?;
+        self.advance();
+
+        // Size (missing for slices)
+        let mut current = self.expect_current(None, Some("]".to_string()))?;
+        let mut size = None;
+        if current.data == Token::SemiColon {
+            self.advance();
+            current = self.expect_current(None, Some("Size".to_string()))?;
+            match &current.data {
+                Token::Decimal(value) | Token::Identifier(value) => size = Some(current.convert(value.clone())),
+                _ => return Err(ParserError::UnexpectedToken(current, Some("Size".to_string())))
+            }
+            self.advance();
+            current = self.expect_current(None, Some("]".to_string()))?;
+        }
+        if current.data != Token::RightBracket {
+            return Err(ParserError::UnexpectedToken(current, Some("]".to_string())));
+        }
+
+        Ok(Positioned::new(TypeExpr::Array { inner: Box::new(inner), size }, start, current.end))
+    }
+
+    fn parse_pointer_type(&mut self, start: Position) -> Result<Positioned<TypeExpr>, ParserError> {
+        self.advance();
+
+        let mut constant = false;
+        if self.expect_current(None, Some("Type".to_string()))?.data == Token::Keyword(Keyword::Const) {
+            constant = true;
+            self.advance();
+        }
+
+        let inner = self.parse_type0()?;
+        let end = inner.end.clone();
+        Ok(Positioned::new(TypeExpr::Pointer { constant, inner: Box::new(inner) }, start, end))
+    }
+
+    fn parse_function_type(&mut self, start: Position) -> Result<Positioned<TypeExpr>, ParserError> {
+        self.advance();
+        self.expect_current(Some(Token::LeftParenthesis), Some("(".to_string()))?;
+        self.advance();
+
+        let (params, current) = self.parse_type_list(Token::RightParenthesis)?;
+        let mut end = current.end;
+
+        // Return type
+        let mut return_type = None;
+        if let Some(next) = self.peek(1) {
+            if next.data == Token::Colon {
+                self.advance_x(2);
+                let data_type = self.parse_type()?;
+                end = data_type.end.clone();
+                return_type = Some(Box::new(data_type));
+            }
+        }
+
+        Ok(Positioned::new(TypeExpr::Function { params, return_type }, start, end))
+    }
+
+    // closure fn(type, ...): type (capturing lambdas)
+    fn parse_closure_type(&mut self, start: Position) -> Result<Positioned<TypeExpr>, ParserError> {
+        self.advance();
+        let function = self.parse_function_type(self.current().unwrap().start)?;
+        let TypeExpr::Function { params, return_type } = function.data else {
+            unreachable!()
+        };
+        let return_type = return_type.unwrap_or_else(|| Box::new(Positioned::new(TypeExpr::Named("void".to_string()), function.end.clone(), function.end.clone())));
+        Ok(Positioned::new(TypeExpr::Closure { params, return_type }, start, function.end))
+    }
+
+    fn parse_named_type(&mut self) -> Result<Positioned<TypeExpr>, ParserError> {
+        let mut name = self.expect_identifier()?;
+
+        // Types of an imported module (module.Type)
+        if let (Some(dot), Some(Positioned { data: Token::Identifier(inner), end, .. })) = (self.peek(1), self.peek(2)) {
+            if dot.data == Token::Dot {
+                self.advance_x(2);
+                name = Positioned::new(format!("{}.{}", name.data, inner), name.start, end);
+            }
+        }
+
+        // Generic parameters
+        if let Some(next) = self.peek(1) {
+            if next.data == Token::LeftBracket {
+                self.advance_x(2);
+                let (params, current) = self.parse_type_list(Token::RightBracket)?;
+                return Ok(Positioned::new(TypeExpr::Generic { name: name.data, params }, name.start, current.end));
+            }
+        }
+
+        Ok(name.convert(TypeExpr::Named(name.data.clone())))
+    }
+
+    fn parse_type0(&mut self) -> Result<Positioned<TypeExpr>, ParserError> {
+        let current = self.expect_current(None, Some("Type".to_string()))?;
+        match current.data {
+            Token::LeftBracket => self.parse_array_type(current.start),
+            Token::LeftParenthesis => self.parse_tuple_type(current.start),
+            Token::Star => self.parse_pointer_type(current.start),
+            Token::Keyword(Keyword::Fn) => self.parse_function_type(current.start),
+            Token::Identifier(name) if name == "closure" && self.peek(1).is_some_and(|next| next.data == Token::Keyword(Keyword::Fn)) => self.parse_closure_type(current.start),
+            _ => self.parse_named_type()
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<Positioned<TypeExpr>, ParserError> {
+        let mut data_type = self.parse_type0()?;
+
+        // Optional suffix
+        while let Some(next) = self.peek(1) {
+            if next.data != Token::Question {
+                break;
+            }
+            self.advance();
+            let start = data_type.start.clone();
+            data_type = Positioned::new(TypeExpr::Optional(Box::new(data_type)), start, next.end);
+        }
+
+        Ok(data_type)
     }
 
     fn parse_variable_definition(&mut self, var_type: Positioned<VarType>) -> Result<Positioned<Node>, ParserError> {
         self.advance();
+        if self.current().is_some_and(|current| current.data == Token::LeftParenthesis) {
+            return self.parse_destructuring(var_type);
+        }
 
         // Get Identifier
         let name = self.expect_identifier()?;
@@ -243,7 +631,7 @@ impl Parser {
         if let Some(current) = current {
             if current.data == Token::Colon {
                 self.advance();
-                data_type = Some(self.expect_identifier()?);
+                data_type = Some(self.parse_type()?);
                 self.advance();
                 end = data_type.as_ref().unwrap().end.clone();
             }
@@ -266,18 +654,47 @@ impl Parser {
             var_type, 
             name, 
             data_type, 
-            value 
+            value,
+            public: false,
+            attributes: Vec::new()
         }, start, end))        
     }
 
-    fn parse_function_definition(&mut self, start: Position, external: bool, constructor: bool) -> Result<Positioned<Node>, ParserError> {
+    // var (name, name, ...): type = value
+    fn parse_destructuring(&mut self, var_type: Positioned<VarType>) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let mut names = Vec::new();
+        let mut current = self.expect_current(None, Some(")".to_string()))?;
+        while current.data != Token::RightParenthesis {
+            if !names.is_empty() {
+                self.expect_current(Some(Token::Comma), Some(",".to_string()))?;
+                self.advance();
+            }
+            names.push(self.expect_identifier()?);
+            self.advance();
+            current = self.expect_current(None, Some(")".to_string()))?;
+        }
         self.advance();
 
-        // Get name
-        let name = self.expect_identifier()?;
+        let mut data_type = None;
+        if self.current().is_some_and(|current| current.data == Token::Colon) {
+            self.advance();
+            data_type = Some(self.parse_type()?);
+            self.advance();
+        }
+
+        self.expect_current(Some(Token::Equal), Some("=".to_string()))?;
         self.advance();
+        let value = self.parse_expr()?;
 
-        // Get parameters
+        let start = var_type.start.clone();
+        let end = value.end.clone();
+        Ok(Positioned::new(Node::Destructuring { var_type, names, data_type, value: Box::new(value) }, start, end))
+    }
+
+    // Parses "(name: type, ...)", the closing parenthesis becomes the current token
+    // "..." ends the parameters of variadic (extern) functions
+    fn parse_parameters(&mut self, variadic_allowed: bool) -> Result<(Vec<FunctionDefinitionParameter>, bool, Positioned<Token>), ParserError> {
         self.expect_current(Some(Token::LeftParenthesis), Some("(".to_string()))?;
         self.advance();
         let mut params = Vec::new();
@@ -288,17 +705,102 @@ impl Parser {
                 self.advance();
             }
 
+            if variadic_allowed && self.expect_current(None, Some("Parameter".to_string()))?.data == Token::Ellipsis {
+                self.advance();
+                current = self.expect_current(Some(Token::RightParenthesis), Some(")".to_string()))?;
+                return Ok((params, true, current));
+            }
+
             let name = self.expect_identifier()?;
             self.advance();
             self.expect_current(Some(Token::Colon), Some(":".to_string()))?;
             self.advance();
-            let data_type= self.expect_identifier()?;
+            let data_type = self.parse_type()?;
 
             params.push(FunctionDefinitionParameter { name, data_type });
 
             self.advance();
             current = self.expect_current(None, Some(")".to_string()))?;
         }
+        Ok((params, false, current))
+    }
+
+    // Parses "=> statements...", end is moved to the end of the last statement
+    fn parse_body(&mut self, end: &mut Position) -> Result<Vec<Positioned<Node>>, ParserError> {
+        self.expect_current(Some(Token::RightDoubleArrow), Some("=>".to_string()))?;
+        self.advance();
+        self.parse_block(end)
+    }
+
+    // Parses the following lines indented deeper than the current one
+    fn parse_block(&mut self, end: &mut Position) -> Result<Vec<Positioned<Node>>, ParserError> {
+        let mut body = Vec::new();
+
+        let mut current_opt = self.current();
+        while let Some(current) = &current_opt {
+            if current.data == Token::NewLine {
+                self.advance();
+                current_opt = self.current();
+                continue;
+            }
+
+            // Count the tabs of the line
+            let mut tabs = 0;
+            while self.peek(tabs).is_some_and(|token| token.data == Token::Tab) {
+                tabs += 1;
+            }
+            if self.peek(tabs).is_some_and(|token| token.data == Token::NewLine) {
+                // Empty line
+                self.advance_x(tabs);
+                current_opt = self.current();
+                continue;
+            }
+            if tabs <= self.indent {
+                break;
+            } 
+
+            self.advance_x(self.indent + 1);
+            
+            self.indent += 1;
+            let node = self.parse_current();
+            self.indent -= 1;
+            let node = node?;
+            *end = node.end.clone();
+            body.push(node);
+            current_opt = self.current();
+        }
+
+        Ok(body)
+    }
+
+    // Destructors are functions named drop without parameters
+    fn parse_destructor(&mut self, keyword: Positioned<Keyword>) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let mut end = keyword.end.clone();
+        let body = self.parse_body(&mut end)?;
+
+        Ok(Positioned::new(Node::FunctionDefinition { 
+            name: keyword.convert("drop".to_string()), 
+            return_type: None, 
+            params: Vec::new(), 
+            body: Some(body),
+            constructor: false,
+            constant: false,
+            variadic: false,
+            public: true,
+            attributes: Vec::new()
+        }, keyword.start, end))
+    }
+
+    fn parse_function_definition(&mut self, start: Position, external: bool, constructor: bool) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+
+        // Get name
+        let name = self.expect_identifier()?;
+        self.advance();
+
+        // Get parameters
+        let (params, variadic, current) = self.parse_parameters(external)?;
         let mut end = current.end.clone();
         self.advance();
 
@@ -309,7 +811,7 @@ impl Parser {
             if let Some(current) = current {
                 if current.data == Token::Colon {
                     self.advance();
-                    data_type = Some(self.expect_identifier()?);
+                    data_type = Some(self.parse_type()?);
                     self.advance();
                     end = data_type.as_ref().unwrap().end.clone();
                 }
@@ -322,32 +824,7 @@ impl Parser {
 
         // Get body
         let body = if !external {
-            let mut body = Vec::new();
-
-            self.expect_current(Some(Token::RightDoubleArrow), Some("=>".to_string()))?;
-            self.advance();
-            let mut current_opt = self.current();
-            
-            while let Some(current) = &current_opt {
-                if current.data == Token::NewLine {
-                    self.advance();
-                    current_opt = self.current();
-                    continue;
-                }
-
-                if current.data != Token::Tab {
-                    break;
-                } 
-
-                self.advance();
-                
-                let node = self.parse_current()?;
-                end = node.end.clone();
-                body.push(node);
-                current_opt = self.current();
-            }
-
-            Some(body)
+            Some(self.parse_body(&mut end)?)
         } else {
             None
         };
@@ -357,7 +834,11 @@ impl Parser {
             return_type: data_type, 
             params, 
             body,
-            constructor 
+            constructor,
+            constant: false,
+            variadic,
+            public: false,
+            attributes: Vec::new()
         }, start, end))
     }
 
@@ -368,6 +849,110 @@ impl Parser {
         Ok(Positioned::new(Node::Return(Box::new(expr)), start, end))
     }
 
+    fn parse_defer(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let expr = self.parse_expr()?;
+        let end = expr.end.clone();
+        Ok(Positioned::new(Node::Defer(Box::new(expr)), start, end))
+    }
+
+    fn parse_if(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+
+        // Condition ("if let name = value" binds the value of an optional)
+        let current = self.expect_current(None, Some("Condition".to_string()))?;
+        let condition = if current.data == Token::Keyword(Keyword::Let) {
+            let definition = self.parse_variable_definition(current.convert(VarType::Constant))?;
+            if !matches!(definition.data, Node::VariableDefinition { value: Some(_), .. }) {
+                return Err(ParserError::UnexpectedToken(self.expect_current(None, Some("=".to_string()))?, Some("=".to_string())));
+            }
+            definition
+        } else {
+            self.parse_expr()?
+        };
+
+        // Body
+        let mut end = condition.end.clone();
+        let body = self.parse_body(&mut end)?;
+
+        // Else on a line with the same indentation
+        let mut else_body = None;
+        let is_else = (0..self.indent).all(|i| self.peek(i).is_some_and(|token| token.data == Token::Tab)) && 
+            self.peek(self.indent).is_some_and(|token| token.data == Token::Keyword(Keyword::Else));
+        if is_else {
+            self.advance_x(self.indent + 1);
+            let current = self.expect_current(None, Some("=>".to_string()))?;
+            if current.data == Token::Keyword(Keyword::If) {
+                let node = self.parse_if(current.start)?;
+                end = node.end.clone();
+                else_body = Some(vec![node]);
+            } else {
+                else_body = Some(self.parse_body(&mut end)?);
+            }
+        }
+
+        Ok(Positioned::new(Node::If { condition: Box::new(condition), body, else_body }, start, end))
+    }
+
+    fn parse_type_alias(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let name = self.expect_identifier()?;
+        self.advance();
+        self.expect_current(Some(Token::Equal), Some("=".to_string()))?;
+        self.advance();
+        let data_type = self.parse_type()?;
+        self.advance();
+        let end = data_type.end.clone();
+        Ok(Positioned::new(Node::TypeAlias { name, data_type, public: false }, start, end))
+    }
+
+    // extern class NAME / extern const NAME: TYPE
+    fn parse_extern_declaration(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        let constant = self.current().is_some_and(|current| current.data == Token::Keyword(Keyword::Const));
+        self.advance();
+        let name = self.expect_identifier()?;
+        self.advance();
+        if !constant {
+            let end = name.end.clone();
+            return Ok(Positioned::new(Node::ExternClass { name, public: false }, start, end));
+        }
+
+        self.expect_current(Some(Token::Colon), Some(":".to_string()))?;
+        self.advance();
+        let data_type = self.parse_type()?;
+        self.advance();
+        let end = data_type.end.clone();
+        Ok(Positioned::new(Node::ExternConstant { name, data_type, public: false }, start, end))
+    }
+
+    fn parse_extern_struct(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let name = self.expect_identifier()?;
+        self.advance();
+
+        // Fields
+        let mut end = name.end.clone();
+        let fields = self.parse_block(&mut end)?;
+
+        Ok(Positioned::new(Node::ExternStruct { name, fields, public: false, attributes: Vec::new() }, start, end))
+    }
+
+    // c "code" or c => followed by lines of code
+    fn parse_inline_c(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let mut end = start.clone();
+        if self.expect_current(None, Some("=>".to_string()))?.data == Token::RightDoubleArrow {
+            let lines = self.parse_body(&mut end)?;
+            return Ok(Positioned::new(Node::InlineC(lines), start, end));
+        }
+
+        let line = self.parse_expr()?;
+        end = line.end.clone();
+        self.expect_end_of_statement()?;
+        self.advance();
+        Ok(Positioned::new(Node::InlineC(vec![line]), start, end))
+    }
+
     fn parse_include(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
         self.advance();
         let path = self.expect_string()?;
@@ -376,6 +961,47 @@ impl Parser {
         Ok(Positioned::new(Node::Include(path), start, end))
     }
 
+    fn parse_import(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let module = self.expect_identifier()?;
+        let mut end = module.end.clone();
+        self.advance();
+
+        // Selected names
+        let mut names = None;
+        if let Some(current) = self.current() {
+            if current.data == Token::Dot {
+                self.advance();
+                self.expect_current(Some(Token::LeftBrace), Some("{".to_string()))?;
+                self.advance();
+                let (list, current) = self.parse_name_list()?;
+                end = current.end.clone();
+                names = Some(list);
+                self.advance();
+            }
+        }
+
+        Ok(Positioned::new(Node::Import { module, names }, start, end))
+    }
+
+    // Parses "name, ...}", the closing brace becomes the current token
+    fn parse_name_list(&mut self) -> Result<(Vec<Positioned<String>>, Positioned<Token>), ParserError> {
+        let mut names = Vec::new();
+        let mut current = self.expect_current(None, Some("}".to_string()))?;
+        while current.data != Token::RightBrace {
+            if !names.is_empty() {
+                self.expect_current(Some(Token::Comma), Some(",".to_string()))?;
+                self.advance();
+            }
+
+            names.push(self.expect_identifier()?);
+
+            self.advance();
+            current = self.expect_current(None, Some("}".to_string()))?;
+        }
+        Ok((names, current))
+    }
+
     fn parse_class_definition(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
         self.advance();
 
@@ -384,35 +1010,114 @@ impl Parser {
         self.advance();
 
         // Body
-        let mut body = Vec::new();
         let mut end = name.end.clone();
+        let body = self.parse_block(&mut end)?;
 
-        let mut current_opt = self.current();
-        while let Some(current) = &current_opt {
-            if current.data == Token::NewLine {
+        Ok(Positioned::new(Node::ClassDefinition { name, body, public: false, attributes: Vec::new() }, start, end))
+    }
+
+    fn parse_public(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let current = self.expect_current(None, Some("Definition".to_string()))?;
+        match current.data {
+            Token::Keyword(Keyword::Fn | Keyword::Extern | Keyword::New | Keyword::Class | Keyword::Const | Keyword::Var | Keyword::Type | Keyword::Static) => {},
+            _ => return Err(ParserError::UnexpectedToken(current, Some("Definition".to_string())))
+        }
+
+        let mut node = self.parse_current()?;
+        match &mut node.data {
+            Node::VariableDefinition { public, .. } |
+            Node::FunctionDefinition { public, .. } |
+            Node::ClassDefinition { public, .. } |
+            Node::TypeAlias { public, .. } |
+            Node::ExternClass { public, .. } |
+            Node::ExternStruct { public, .. } |
+            Node::ExternConstant { public, .. } => *public = true,
+            Node::Static(node) => match &mut node.data {
+                Node::VariableDefinition { public, .. } |
+                Node::FunctionDefinition { public, .. } => *public = true,
+                _ => unreachable!()
+            },
+            _ => unreachable!()
+        }
+        node.start = start;
+        Ok(node)
+    }
+
+    // Attributes are written before the definition, on the same line or the lines above
+    fn parse_attributes(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        let mut attributes = Vec::new();
+        while self.current().is_some_and(|current| current.data == Token::At) {
+            self.advance();
+            let name = self.expect_identifier()?;
+            self.advance();
+
+            let mut value = None;
+            if self.current().is_some_and(|current| current.data == Token::LeftParenthesis) {
+                self.advance();
+                value = Some(self.expect_string()?);
+                self.advance();
+                self.expect_current(Some(Token::RightParenthesis), Some(")".to_string()))?;
                 self.advance();
-                current_opt = self.current();
-                continue;
             }
+            attributes.push(Attribute { name, value });
 
-            if current.data != Token::Tab {
-                break;
-            } 
+            if self.current().is_some_and(|current| current.data == Token::NewLine) {
+                self.advance();
+                // The next line has the same indentation
+                for _ in 0..self.indent {
+                    self.expect_current(Some(Token::Tab), Some("Tab".to_string()))?;
+                    self.advance();
+                }
+            }
+        }
 
-            self.advance();
-            
-            let node = self.parse_current()?;
-            end = node.end.clone();
-            body.push(node);
-            current_opt = self.current();
+        let current = self.expect_current(None, Some("Definition".to_string()))?;
+        match current.data {
+            Token::Keyword(Keyword::Fn | Keyword::New | Keyword::Class | Keyword::Const | Keyword::Var | Keyword::Static | Keyword::Pub | Keyword::Extern) => {},
+            _ => return Err(ParserError::UnexpectedToken(current, Some("Definition".to_string())))
+        }
+
+        let mut node = self.parse_current()?;
+        let definition = match &mut node.data {
+            Node::Static(node) => &mut node.data,
+            node => node
+        };
+        match definition {
+            Node::VariableDefinition { attributes: node_attributes, .. } |
+            Node::FunctionDefinition { attributes: node_attributes, .. } |
+            Node::ClassDefinition { attributes: node_attributes, .. } |
+            Node::ExternStruct { attributes: node_attributes, .. } => *node_attributes = attributes,
+            _ => return Err(ParserError::UnexpectedToken(current, Some("Definition".to_string())))
         }
+        node.start = start;
+        Ok(node)
+    }
 
-        Ok(Positioned::new(Node::ClassDefinition { name, body }, start, end))
+    fn parse_static(&mut self, start: Position) -> Result<Positioned<Node>, ParserError> {
+        self.advance();
+        let current = self.expect_current(None, Some("Definition".to_string()))?;
+        match current.data {
+            Token::Keyword(Keyword::Fn | Keyword::Const | Keyword::Var) => {},
+            _ => return Err(ParserError::UnexpectedToken(current, Some("Definition".to_string())))
+        }
+
+        let node = self.parse_current()?;
+        let end = node.end.clone();
+        Ok(Positioned::new(Node::Static(Box::new(node)), start, end))
     }
 
     fn handle_keyword(&mut self, keyword: Positioned<Keyword>) -> Result<Positioned<Node>, ParserError> {
         match keyword.data {
             Keyword::Fn => self.parse_function_definition(keyword.start.clone(), false, false),
+            Keyword::Const if self.peek(1).is_some_and(|next| next.data == Token::Keyword(Keyword::Fn)) => {
+                self.advance();
+                let mut res = self.parse_function_definition(keyword.start.clone(), false, false)?;
+                if let Node::FunctionDefinition { constant, .. } = &mut res.data {
+                    *constant = true;
+                }
+                Ok(res)
+            }
             Keyword::Const => {
                 let res = self.parse_variable_definition(keyword.convert(VarType::Constant))?;
                 self.expect_end_of_statement()?;
@@ -431,11 +1136,38 @@ impl Parser {
                 self.advance();
                 Ok(res)
             }
+            Keyword::Defer => {
+                let res = self.parse_defer(keyword.start.clone())?;
+                self.expect_end_of_statement()?;
+                self.advance();
+                Ok(res)
+            }
+            Keyword::If => self.parse_if(keyword.start.clone()),
+            Keyword::Unsafe => {
+                self.advance();
+                let mut end = keyword.end.clone();
+                let body = self.parse_body(&mut end)?;
+                Ok(Positioned::new(Node::Unsafe(body), keyword.start, end))
+            },
+            Keyword::Else |
+            Keyword::Let |
+            Keyword::Struct |
+            Keyword::As => Err(ParserError::UnexpectedToken(self.current().unwrap(), None)),
             Keyword::Extern => {
                 let start = keyword.start.clone();
                 self.advance();
-                self.expect_current(Some(Token::Keyword(Keyword::Fn)), Some("fn".to_string()))?;
-                self.parse_function_definition(start, true, false)
+                let current = self.expect_current(None, Some("fn".to_string()))?;
+                match current.data {
+                    Token::Keyword(Keyword::Fn) => self.parse_function_definition(start, true, false),
+                    Token::Keyword(Keyword::Struct) => self.parse_extern_struct(start),
+                    Token::Keyword(Keyword::Class | Keyword::Const) => {
+                        let res = self.parse_extern_declaration(start)?;
+                        self.expect_end_of_statement()?;
+                        self.advance();
+                        Ok(res)
+                    },
+                    _ => Err(ParserError::UnexpectedToken(current, Some("fn, class, struct or const".to_string())))
+                }
             },
             Keyword::Include => {
                 let res = self.parse_include(keyword.start.clone())?;
@@ -443,9 +1175,28 @@ impl Parser {
                 self.advance();
                 Ok(res)
             },
+            Keyword::Import => {
+                let res = self.parse_import(keyword.start.clone())?;
+                self.expect_end_of_statement()?;
+                self.advance();
+                Ok(res)
+            },
             Keyword::Class => self.parse_class_definition(keyword.start.clone()),
-            Keyword::This => self.parse_expr(),
+            Keyword::Type => {
+                let res = self.parse_type_alias(keyword.start.clone())?;
+                self.expect_end_of_statement()?;
+                self.advance();
+                Ok(res)
+            },
+            Keyword::This |
+            Keyword::Null |
+            Keyword::None |
+            Keyword::True |
+            Keyword::False => self.parse_expr(),
             Keyword::New => self.parse_function_definition(keyword.start.clone(), false, true),
+            Keyword::Pub => self.parse_public(keyword.start.clone()),
+            Keyword::Static => self.parse_static(keyword.start.clone()),
+            Keyword::Drop => self.parse_destructor(keyword),
         }
     } 
 
@@ -453,18 +1204,27 @@ impl Parser {
         let current = self.current();
         if let Some(current) = current {
             match &current.data {
+                // c "code" (not a variable followed by a string)
+                Token::Identifier(name) if name == "c" && self.peek(1).is_some_and(|next| matches!(next.data, Token::String(_) | Token::InterpolationStart | Token::RightDoubleArrow)) => {
+                    let start = current.start.clone();
+                    self.parse_inline_c(start)
+                },
                 Token::Decimal(_) |
                 Token::String(_) |
-                Token::Identifier(_) => {
+                Token::InterpolationStart |
+                Token::Identifier(_) |
+                Token::Star |
+                Token::Ampersand |
+                Token::LeftParenthesis => {
                     let res = self.parse_expr()?;
                     self.expect_end_of_statement()?;
                     self.advance();
                     Ok(res)
                 }
                 Token::Keyword(keyword) => self.handle_keyword(current.convert(keyword.clone())),
+                Token::At => self.parse_attributes(current.start.clone()),
                 Token::Plus |
                 Token::Dash => todo!("Unary"),
-                Token::LeftParenthesis => todo!("expr - parenthesis"),
                 Token::NewLine | Token::Tab => {
                     self.advance();
                     self.parse_current()
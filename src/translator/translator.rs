@@ -1,9 +1,11 @@
-use crate::{util::{file::SourceFile, position::Positioned}, parser::node::{Node, ValueNode, Operator, VarType, FunctionCallParameter}};
+use crate::{util::{file::SourceFile, position::Positioned}, parser::node::{Node, ValueNode, InterpolationPart, Operator, UnaryOperator, VarType, FunctionCallParameter, FunctionDefinitionParameter, TypeExpr, Attribute}};
 
 pub struct CFile {
     pub name: String,
     pub header: String,
-    pub src: String
+    pub declarations: String,
+    pub src: String,
+    pub prelude: Vec<String>
 }
 
 impl CFile {
@@ -12,7 +14,9 @@ impl CFile {
         Self {
             name,
             header: String::new(),
-            src: String::new()
+            declarations: String::new(),
+            src: String::new(),
+            prelude: Vec::new()
         }
     }
 
@@ -38,7 +42,13 @@ impl CProject {
         for f in self.files.iter_mut() {
             if f.name == file.name {
                 f.header.push_str(&file.header);
+                f.declarations.push_str(&file.declarations);
                 f.src.push_str(&file.src);
+                for definition in file.prelude {
+                    if !f.prelude.contains(&definition) {
+                        f.prelude.push(definition);
+                    }
+                }
                 return;
             }
         }
@@ -48,6 +58,16 @@ impl CProject {
 
     pub fn post_process(&mut self) {
         for file in self.files.iter_mut() {
+            if !file.prelude.is_empty() {
+                file.header = format!("{}{}", file.prelude.join(""), file.header);
+            }
+            // Headers include each other through imports
+            file.header = format!("#pragma once\n{}", file.header);
+            if !file.declarations.is_empty() {
+                // Private functions are only declared in the .c (after the includes)
+                let includes = file.src.lines().take_while(|line| line.starts_with("#include")).map(|line| line.len() + 1).sum();
+                file.src.insert_str(includes, &file.declarations);
+            }
             if !file.header.is_empty() && !file.src.is_empty() {
                 file.src = format!("#include \"{}.h\"\n{}", file.name, file.src);
             }
@@ -58,16 +78,34 @@ impl CProject {
 pub struct Translator {
     src: SourceFile,
     ast: Vec<Positioned<Node>>,
-    index: usize
+    index: usize,
+    prelude: Vec<String>,
+    classes: Vec<String>,
+    structs: Vec<String>, // extern structs
+    imports: Vec<String>,
+    aliases: Vec<(String, Positioned<TypeExpr>)>,
+    lifted: Vec<String>,
+    lambdas: usize,
+    class: Option<String>, // Class being translated (Self)
+    bounds_checks: bool
 }
 
 impl Translator {
 
-    pub fn new(src: SourceFile, ast: Vec<Positioned<Node>>) -> Self {
+    pub fn new(src: SourceFile, ast: Vec<Positioned<Node>>, classes: Vec<String>, structs: Vec<String>, bounds_checks: bool) -> Self {
         Self {
             src,
             ast,
-            index: 0
+            index: 0,
+            prelude: Vec::new(),
+            classes,
+            structs,
+            imports: Vec::new(),
+            aliases: Vec::new(),
+            lifted: Vec::new(),
+            lambdas: 0,
+            class: None,
+            bounds_checks
         }
     }
 
@@ -83,16 +121,203 @@ impl Translator {
         self.ast.get(self.index).cloned()
     }
 
-    fn translate_type(&mut self, data_type: Option<Positioned<String>>) -> String {
+    // Adds a definition (guarded against redefinition) to the header of the current file
+    fn require(&mut self, definition: String) {
+        if !self.prelude.contains(&definition) {
+            self.prelude.push(definition);
+        }
+    }
+
+    // Turns a C type into an identifier (used to name the generated types)
+    fn mangle(name: &mut String, c_type: &str) {
+        for chr in c_type.replace('*', " ptr").chars() {
+            if chr.is_alphanumeric() {
+                name.push(chr);
+            } else if !name.ends_with('_') {
+                name.push('_');
+            }
+        }
+        while name.ends_with('_') {
+            name.pop();
+        }
+    }
+
+    fn translate_slice_type(&mut self, element: Positioned<TypeExpr>) -> String {
+        let element_type = self.translate_declaration(element.clone(), "");
+        let mut name = "apla_slice_".to_string();
+        Self::mangle(&mut name, &element_type);
+        let pointer = self.translate_declaration(element, "*ptr");
+
+        let guard = name.to_uppercase();
+        self.require(format!("#ifndef {guard}\n#define {guard}\n#include <stddef.h>\ntypedef struct {{ {pointer}; size_t len; }} {name};\n#endif\n"));
+
+        name
+    }
+
+    // Closures are a function taking the environment first and the environment itself
+    fn translate_closure_type(&mut self, position: Positioned<()>, params: Vec<Positioned<TypeExpr>>, return_type: Positioned<TypeExpr>) -> String {
+        let mut name = "apla_closure".to_string();
+        if params.is_empty() {
+            name.push_str("_void");
+        }
+        for param in params.iter() {
+            name.push('_');
+            let param = self.translate_declaration(param.clone(), "");
+            Self::mangle(&mut name, &param);
+        }
+        name.push_str("_to_");
+        let return_c_type = self.translate_declaration(return_type.clone(), "");
+        Self::mangle(&mut name, &return_c_type);
+
+        let env = position.convert(TypeExpr::Pointer { constant: false, inner: Box::new(position.convert(TypeExpr::Named("c_void".to_string()))) });
+        let call = self.translate_declaration(position.convert(TypeExpr::Function { 
+            params: std::iter::once(env).chain(params).collect(), 
+            return_type: Some(Box::new(return_type)) 
+        }), "call");
+
+        let guard = name.to_uppercase();
+        self.require(format!("#ifndef {guard}\n#define {guard}\ntypedef struct {{ {call}; void* env; }} {name};\n#endif\n"));
+
+        name
+    }
+
+    // One struct per tuple shape, its elements are the fields _0, _1, ...
+    fn translate_tuple_type(&mut self, elements: Vec<Positioned<TypeExpr>>) -> String {
+        let mut name = "apla_tuple".to_string();
+        let mut fields = String::new();
+        for (index, element) in elements.into_iter().enumerate() {
+            name.push('_');
+            let element_type = self.translate_declaration(element.clone(), "");
+            Self::mangle(&mut name, &element_type);
+            fields.push_str(&self.translate_declaration(element, &format!("_{}", index)));
+            fields.push_str("; ");
+        }
+
+        let guard = name.to_uppercase();
+        self.require(format!("#ifndef {guard}\n#define {guard}\ntypedef struct {{ {fields}}} {name};\n#endif\n"));
+
+        name
+    }
+
+    // Results are tagged structs (without value for Result[void, E])
+    fn translate_result_type(&mut self, value: Positioned<TypeExpr>, error: Positioned<TypeExpr>) -> String {
+        let mut name = "apla_result_".to_string();
+        let value_type = self.translate_declaration(value.clone(), "");
+        Self::mangle(&mut name, &value_type);
+        name.push('_');
+        let error_type = self.translate_declaration(error.clone(), "");
+        Self::mangle(&mut name, &error_type);
+
+        let mut fields = "int ok; ".to_string();
+        if value_type != "void" {
+            fields.push_str(&self.translate_declaration(value, "value"));
+            fields.push_str("; ");
+        }
+        fields.push_str(&self.translate_declaration(error, "error"));
+
+        let guard = name.to_uppercase();
+        self.require(format!("#ifndef {guard}\n#define {guard}\ntypedef struct {{ {fields}; }} {name};\n#endif\n"));
+
+        name
+    }
+
+    // Files using an alias get its typedef
+    fn translate_alias(&mut self, name: &str) -> String {
+        if let Some((_, data_type)) = self.aliases.iter().find(|(x, _)| x == name).cloned() {
+            let declaration = self.translate_declaration(data_type, name);
+            let guard = format!("APLA_TYPE_{}", name.to_uppercase());
+            self.require(format!("#ifndef {guard}\n#define {guard}\ntypedef {declaration};\n#endif\n"));
+        }
+        name.to_string()
+    }
+
+    // Builds a C declaration, the name ends up inside of the type for arrays, pointers and functions
+    fn translate_declaration(&mut self, data_type: Positioned<TypeExpr>, name: &str) -> String {
+        match data_type.data {
+            TypeExpr::Array { inner, size: Some(size) } => {
+                let name = if name.contains('*') {
+                    format!("({})", name)
+                } else {
+                    name.to_string()
+                };
+                self.translate_declaration(*inner, &format!("{}[{}]", name, size.data))
+            },
+            TypeExpr::Pointer { constant, inner } => {
+                let name = if constant {
+                    format!("const *{}", name)
+                } else {
+                    format!("*{}", name)
+                };
+                self.translate_declaration(*inner, &name)
+            },
+            TypeExpr::Optional(inner) => self.translate_declaration(*inner, name),
+            TypeExpr::Function { params, return_type } => {
+                let mut params: Vec<String> = params.into_iter().map(|param| self.translate_declaration(param, "")).collect();
+                if params.is_empty() {
+                    params.push("void".to_string());
+                }
+                let name = format!("(*{})({})", name, params.join(", "));
+                if let Some(return_type) = return_type {
+                    self.translate_declaration(*return_type, &name)
+                } else {
+                    format!("void {}", name)
+                }
+            },
+            _ => {
+                let mut str = self.translate_type(Some(data_type));
+                let glued = name.starts_with('[') || (str.ends_with('*') && name.starts_with('*'));
+                if !name.is_empty() && !glued {
+                    str.push(' ');
+                }
+                str.push_str(name);
+                str
+            }
+        }
+    }
+
+    fn translate_type(&mut self, data_type: Option<Positioned<TypeExpr>>) -> String {
         if let Some(data_type) = data_type {
-            match data_type.data.as_str() {
-                "c_char" => "char".to_string(),
-                "c_short" => "short".to_string(),
-                "c_int" => "int".to_string(),
-                "c_long" => "long".to_string(),
-                "c_float" => "float".to_string(),
-                "c_double" => "double".to_string(),
-                _ => data_type.data.clone()
+            match &data_type.data {
+                // Imported types (module.Type) share the C namespace
+                TypeExpr::Named(name) => match name.split_once('.').map_or(name.as_str(), |(_, name)| name) {
+                    "c_char" => "char".to_string(),
+                    "c_short" => "short".to_string(),
+                    "c_int" => "int".to_string(),
+                    "c_long" => "long".to_string(),
+                    "c_float" => "float".to_string(),
+                    "c_double" => "double".to_string(),
+                    "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" => {
+                        self.require("#include <stdint.h>\n".to_string());
+                        let (sign, bits) = name.split_at(1);
+                        format!("{}int{}_t", if sign == "u" { "u" } else { "" }, bits)
+                    },
+                    "usize" | "isize" => {
+                        self.require("#include <stddef.h>\n".to_string());
+                        if name == "usize" { "size_t" } else { "ptrdiff_t" }.to_string()
+                    },
+                    "f32" => "float".to_string(),
+                    "f64" => "double".to_string(),
+                    "c_string" => "char*".to_string(),
+                    "c_void" => "void".to_string(),
+                    "bool" => {
+                        self.require("#include <stdbool.h>\n".to_string());
+                        "bool".to_string()
+                    },
+                    "Self" => match &self.class {
+                        Some(class) => format!("{}*", class),
+                        None => panic!("Unexpected Self outside of a class!")
+                    },
+                    name if self.structs.iter().any(|x| x == name) => format!("struct {}", name),
+                    name if self.classes.iter().any(|x| x == name) => format!("{}*", name), // Class values are pointers
+                    name if self.aliases.iter().any(|(x, _)| x == name) => self.translate_alias(name),
+                    name => name.to_string()
+                },
+                TypeExpr::Array { inner, size: None } => self.translate_slice_type(*inner.clone()),
+                TypeExpr::Closure { params, return_type } => self.translate_closure_type(data_type.convert(()), params.clone(), *return_type.clone()),
+                TypeExpr::Tuple(elements) => self.translate_tuple_type(elements.clone()),
+                TypeExpr::Generic { name, params } if name == "Result" => self.translate_result_type(params[0].clone(), params[1].clone()),
+                TypeExpr::Generic { .. } => panic!("Cannot translate generic type '{}'", data_type.data),
+                _ => self.translate_declaration(data_type, "")
             }
         } else {
             "void".to_string()
@@ -101,10 +326,20 @@ impl Translator {
 
     fn translate_value_node(&mut self, value: Positioned<ValueNode>) -> String {
         match value.data {
-            ValueNode::Decimal(x) => x.clone(),
+            ValueNode::Decimal(x) |
+            ValueNode::Float(x) => x.clone(),
             ValueNode::String(x) => format!("\"{}\"", x),
+            ValueNode::Bool(x) => {
+                self.require("#include <stdbool.h>\n".to_string());
+                x.to_string()
+            },
             ValueNode::VariableCall(x) => x.clone(),
             ValueNode::This => "self".to_string(),
+            ValueNode::Null |
+            ValueNode::None => {
+                self.require("#include <stddef.h>\n".to_string());
+                "NULL".to_string()
+            },
         }
     }
 
@@ -120,6 +355,15 @@ impl Translator {
             Operator::Multiply => str.push_str(" * "),
             Operator::Divide => str.push_str(" / "),
             Operator::MemberAccess => str.push_str("->"),
+            Operator::FieldAccess => str.push('.'),
+            Operator::Equal => str.push_str(" == "),
+            Operator::NotEqual => str.push_str(" != "),
+            Operator::Less => str.push_str(" < "),
+            Operator::LessEqual => str.push_str(" <= "),
+            Operator::Greater => str.push_str(" > "),
+            Operator::GreaterEqual => str.push_str(" >= "),
+            Operator::And => str.push_str(" && "),
+            Operator::Or => str.push_str(" || "),
             Operator::Assignment => str.push_str(" = "),
         }
 
@@ -129,24 +373,171 @@ impl Translator {
         str
     }
 
-    fn translate_variable_definition(&mut self, var_type: Positioned<VarType>, name: Positioned<String>, data_type: Option<Positioned<String>>, value: Option<Box<Positioned<Node>>>) -> String {
+    fn translate_unary_op(&mut self, op: Positioned<UnaryOperator>, value: Positioned<Node>) -> String {
+        let mut str = String::new();
+
+        str.push('(');
+        match op.data {
+            UnaryOperator::AddressOf => str.push('&'),
+            UnaryOperator::Dereference => str.push('*'),
+            UnaryOperator::Not => str.push('!'),
+        }
+        str.push_str(&self.translate_node(value));
+        str.push(')');
+
+        str
+    }
+
+    fn translate_variable_definition(&mut self, var_type: Positioned<VarType>, name: Positioned<String>, data_type: Option<Positioned<TypeExpr>>, value: Option<Box<Positioned<Node>>>) -> String {
         let mut str = String::new();
         
         _ = var_type; // Vartype is ignored
 
         if let Some(data_type) = data_type {
-            str.push_str(&self.translate_type(Some(data_type)));
+            str.push_str(&self.translate_declaration(data_type, &name.data));
         } else {
             panic!("Missing data_type");
         }
 
-        str.push(' ');
-        str.push_str(&name.data);
-
         if let Some(value) = value {
             str.push_str(" = ");
-            str.push_str(&self.translate_node(*value));
+            str.push_str(&self.translate_initializer(*value));
+        }
+
+        str
+    }
+
+    // Arrays can only be initialized with braces
+    fn translate_initializer(&mut self, node: Positioned<Node>) -> String {
+        if let Node::Array { data_type: _, values } | Node::Tuple { data_type: _, values } = node.data {
+            let values: Vec<String> = values.into_iter().map(|value| self.translate_initializer(value)).collect();
+            format!("{{{}}}", values.join(", "))
+        } else {
+            self.translate_node(node)
+        }
+    }
+
+    fn translate_array(&mut self, position: Positioned<()>, data_type: Option<Positioned<TypeExpr>>, values: Vec<Positioned<Node>>) -> String {
+        let Some(data_type) = data_type else {
+            panic!("Missing data_type");
+        };
+        let data_type = self.translate_declaration(data_type, "");
+        
+        format!("({}){}", data_type, self.translate_initializer(position.convert(Node::Array { data_type: None, values })))
+    }
+
+    fn translate_index(&mut self, value: Positioned<Node>, index: Positioned<Node>, length: Option<Box<Positioned<Node>>>) -> String {
+        let value = self.translate_node(value);
+        let position = index.convert(());
+        let mut index = self.translate_node(index);
+
+        if let (true, Some(length)) = (self.bounds_checks, length) {
+            self.require(BOUNDS_CHECK.to_string());
+            let length = self.translate_node(*length);
+            index = format!("apla_bounds_check({}, {}, \"{}.apla:{}\")", index, length, self.src.name, position.start);
+        }
+
+        format!("{}[{}]", value, index)
+    }
+
+    fn translate_slice(&mut self, data_type: Positioned<TypeExpr>, value: Positioned<Node>, length: Positioned<Node>) -> String {
+        let data_type = self.translate_type(Some(data_type));
+        let value = self.translate_node(value);
+        let length = self.translate_node(length);
+
+        format!("({}){{ {}, {} }}", data_type, value, length)
+    }
+
+    // Lambdas are lifted into static functions, captured variables are copied into an environment
+    fn translate_lambda(&mut self, position: Positioned<()>, params: Vec<FunctionDefinitionParameter>, return_type: Option<Positioned<TypeExpr>>, body: Positioned<Node>, captures: Vec<FunctionDefinitionParameter>) -> String {
+        let Some(return_type) = return_type else {
+            panic!("Missing return_type");
+        };
+        let name = format!("{}_lambda_{}", self.src.name, self.lambdas);
+        self.lambdas += 1;
+        
+        let mut fun_header = String::new();
+        let mut param_types = Vec::new();
+        fun_header.push_str("static ");
+        fun_header.push_str(&self.translate_type(Some(return_type.clone())));
+        fun_header.push(' ');
+        fun_header.push_str(&name);
+        fun_header.push('(');
+        let mut index = 0;
+        if !captures.is_empty() {
+            fun_header.push_str("void* apla_env");
+            index += 1;
+        }
+        for param in params {
+            if index != 0 {
+                fun_header.push_str(", ");
+            }
+            param_types.push(param.data_type.clone());
+            fun_header.push_str(&self.translate_declaration(param.data_type, &param.name.data));
+            index += 1;
+        }
+        if index == 0 {
+            fun_header.push_str("void");
+        }
+        fun_header.push(')');
+
+        let mut str = String::new();
+        let mut lifted = String::new();
+        let env = format!("{}_env", name);
+        if !captures.is_empty() {
+            // The environment
+            lifted.push_str("typedef struct {");
+            for capture in captures.iter() {
+                lifted.push(' ');
+                lifted.push_str(&self.translate_declaration(capture.data_type.clone(), &capture.name.data));
+                lifted.push(';');
+            }
+            lifted.push_str(" } ");
+            lifted.push_str(&env);
+            lifted.push_str(";\n");
+        }
+
+        lifted.push_str(&fun_header);
+        lifted.push_str(" {\n");
+        for capture in captures.iter() {
+            lifted.push('\t');
+            lifted.push_str(&self.translate_declaration(capture.data_type.clone(), &capture.name.data));
+            lifted.push_str(&format!(" = (({}*)apla_env)->{};\n", env, capture.name.data));
         }
+        lifted.push('\t');
+        if !matches!(&return_type.data, TypeExpr::Named(name) if name == "void") {
+            lifted.push_str("return ");
+        }
+        lifted.push_str(&self.translate_node(body));
+        lifted.push_str(";\n}\n");
+        self.lifted.push(lifted);
+
+        if captures.is_empty() {
+            return name;
+        }
+
+        // The closure value, the environment lives as long as the enclosing block
+        let closure_type = self.translate_closure_type(position, param_types, return_type);
+        str.push_str(&format!("({}){{ {}, &({}){{ ", closure_type, name, env));
+        for (i, capture) in captures.iter().enumerate() {
+            if i != 0 {
+                str.push_str(", ");
+            }
+            str.push_str(&capture.name.data);
+        }
+        str.push_str(" } }");
+
+        str
+    }
+
+    fn translate_closure_call(&mut self, value: Positioned<Node>, params: Vec<FunctionCallParameter>) -> String {
+        let value = self.translate_node(value);
+        let mut str = format!("{}.call({}.env", value, value);
+        for param in params {
+            str.push_str(", ");
+            str.push_str(&self.translate_node(param.value));
+        }
+        str.push(')');
 
         str
     }
@@ -160,6 +551,137 @@ impl Translator {
         str
     }
 
+    // One indented line per C line, blocks are not followed by a semicolon
+    fn translate_statements(&mut self, body: Vec<Positioned<Node>>) -> String {
+        let mut str = String::new();
+        for node in body {
+            let statement = match node.data {
+                Node::If { condition, body, else_body } => self.translate_if(*condition, body, else_body),
+                Node::Unsafe(body) => format!("{{\n{}}}", self.translate_statements(body)),
+                Node::InlineC(lines) => self.translate_inline_c(lines),
+                _ => format!("{};", self.translate_node(node))
+            };
+            for line in statement.lines() {
+                str.push('\t');
+                str.push_str(line);
+                str.push('\n');
+            }
+        }
+        str
+    }
+
+    // Copied as written ("\"" is a quote), the variables by their C name
+    fn translate_inline_c(&mut self, lines: Vec<Positioned<Node>>) -> String {
+        let mut str = String::new();
+        for line in lines {
+            let Node::Interpolation(parts) = line.data else {
+                panic!("Unexpected inline C line {}!", line.data);
+            };
+            for part in parts {
+                match part {
+                    InterpolationPart::Text(code) => str.push_str(&code.replace("\\\"", "\"")),
+                    InterpolationPart::Value(value, _) => str.push_str(&self.translate_node(*value))
+                }
+            }
+            str.push('\n');
+        }
+        str
+    }
+
+    fn translate_if(&mut self, condition: Positioned<Node>, body: Vec<Positioned<Node>>, else_body: Option<Vec<Positioned<Node>>>) -> String {
+        let mut str = String::new();
+
+        // "if let" declares the binding in a surrounding block
+        let binding = match &condition.data {
+            Node::VariableDefinition { name, .. } => Some(name.data.clone()),
+            _ => None
+        };
+        let condition = match binding {
+            Some(name) => {
+                str.push_str("{\n\t");
+                str.push_str(&self.translate_node(condition));
+                str.push_str(";\n\t");
+                self.require("#include <stddef.h>\n".to_string());
+                format!("{} != NULL", name)
+            },
+            None => self.translate_node(condition)
+        };
+
+        let mut if_str = format!("if ({}) {{\n{}}}", condition, self.translate_statements(body));
+        match else_body {
+            Some(mut else_body) if else_body.len() == 1 && matches!(else_body[0].data, Node::If { .. }) => {
+                let Node::If { condition, body, else_body } = else_body.remove(0).data else {
+                    unreachable!()
+                };
+                if_str.push_str(" else ");
+                if_str.push_str(&self.translate_if(*condition, body, else_body));
+            },
+            Some(else_body) => if_str.push_str(&format!(" else {{\n{}}}", self.translate_statements(else_body))),
+            None => {}
+        }
+
+        if str.is_empty() {
+            return if_str;
+        }
+        str.push_str(&if_str.replace('\n', "\n\t"));
+        str.push_str("\n}");
+        str
+    }
+
+    // Format string followed by the values (the arguments of printf)
+    fn translate_interpolation(&mut self, parts: Vec<InterpolationPart>) -> String {
+        self.require("#include <stdio.h>\n".to_string());
+        let mut format = String::new();
+        let mut values = Vec::new();
+        for part in parts {
+            match part {
+                InterpolationPart::Text(text) => format.push_str(&text.replace('%', "%%")),
+                InterpolationPart::Value(value, data_type) => {
+                    let c_type = self.translate_type(data_type);
+                    let value = self.translate_node(*value);
+                    let specifier = match c_type.as_str() {
+                        "char" => "%c",
+                        "short" => "%hd",
+                        "int" => "%d",
+                        "long" => "%ld",
+                        "float" | "double" => "%f",
+                        "size_t" => "%zu",
+                        "ptrdiff_t" => "%td",
+                        c_type if c_type.ends_with("int8_t") || c_type.ends_with("int16_t") || c_type.ends_with("int32_t") || c_type.ends_with("int64_t") => {
+                            // The exact specifiers are macros of inttypes.h
+                            self.require("#include <inttypes.h>\n".to_string());
+                            let (sign, bits) = c_type.trim_end_matches("_t").split_once("int").unwrap();
+                            format.push_str(&format!("%\" PRI{}{} \"", if sign == "u" { "u" } else { "d" }, bits));
+                            values.push(value);
+                            continue;
+                        },
+                        "char*" | "const char*" => "%s",
+                        "bool" => {
+                            values.push(format!("{} ? \"true\" : \"false\"", value));
+                            format.push_str("%s");
+                            continue;
+                        },
+                        c_type if c_type.ends_with('*') => {
+                            values.push(format!("(void*){}", value));
+                            format.push_str("%p");
+                            continue;
+                        },
+                        c_type => panic!("No format specifier for the C type '{}'!", c_type)
+                    };
+                    format.push_str(specifier);
+                    values.push(value);
+                }
+            }
+        }
+
+        let mut str = format!("\"{}\"", format);
+        for value in values {
+            str.push_str(", ");
+            str.push_str(&value);
+        }
+        str
+    }
+
     fn translate_function_call(&mut self, name: Positioned<String>, params: Vec<FunctionCallParameter>) -> String {
         let mut str = String::new();
 
@@ -182,19 +704,89 @@ impl Translator {
         match node.data.clone() {
             Node::Value(value) => self.translate_value_node(node.convert(value)),
             Node::BinaryOperation { lhs, op, rhs } => self.translate_binary_op(*lhs, op, *rhs),
-            Node::VariableDefinition { var_type, name, data_type, value } => self.translate_variable_definition(var_type, name, data_type, value),
+            Node::UnaryOperation { op, value } => self.translate_unary_op(op, *value),
+            Node::VariableDefinition { var_type, name, data_type, value, .. } => self.translate_variable_definition(var_type, name, data_type, value),
             Node::Return(value) => self.translate_return(*value),
             Node::FunctionCall { name, params } => self.translate_function_call(name, params),
+            Node::Array { data_type, values } => self.translate_array(node.convert(()), data_type, values),
+            Node::Tuple { data_type, values } => self.translate_array(node.convert(()), data_type, values),
+            Node::Index { value, index, length } => self.translate_index(*value, *index, length),
+            Node::Slice { data_type, value, length } => self.translate_slice(data_type, *value, *length),
+            Node::Lambda { params, return_type, body, captures } => self.translate_lambda(node.convert(()), params, return_type, *body, captures),
+            Node::ClosureCall { value, params } => self.translate_closure_call(*value, params),
+            Node::Interpolation(parts) => self.translate_interpolation(parts),
+            Node::Cast { value, data_type } => format!("(({}){})", self.translate_declaration(data_type, ""), self.translate_node(*value)),
+            Node::Result { data_type, ok, value } => {
+                let mut str = format!("({}){{ .ok = {}", self.translate_type(data_type), ok as u8);
+                if let Some(value) = value {
+                    str.push_str(if ok { ", .value = " } else { ", .error = " });
+                    str.push_str(&self.translate_node(*value));
+                }
+                str.push_str(" }");
+                str
+            },
             _ => panic!("Unexpected node {}!", node.data)
         }
     }
 
+    // GCC attributes (before the declaration) and the exported symbol name (after the declarator)
+    fn translate_attributes(&self, attributes: &[Attribute]) -> (String, String) {
+        let mut qualifiers = String::new();
+        let mut symbol = String::new();
+        for attribute in attributes {
+            match (attribute.name.data.as_str(), &attribute.value) {
+                ("cold", _) => qualifiers.push_str("__attribute__((cold)) "),
+                ("packed", _) => qualifiers.push_str("__attribute__((packed)) "),
+                ("deprecated", Some(message)) => qualifiers.push_str(&format!("__attribute__((deprecated(\"{}\"))) ", message.data)),
+                ("deprecated", None) => qualifiers.push_str("__attribute__((deprecated)) "),
+                ("export", Some(name)) => symbol = format!(" __asm__(\"{}\")", name.data),
+                _ => {}
+            }
+        }
+        (qualifiers, symbol)
+    }
+
+    // Only on the definition, the declarations without inline make it an external definition
+    fn translate_inline(attributes: &[Attribute]) -> &'static str {
+        if attributes.iter().any(|attribute| attribute.name.data == "inline") {
+            "inline "
+        } else {
+            ""
+        }
+    }
+
     fn translate_root(&mut self, root: Positioned<Node>) -> CFile {
         match root.data {
-            Node::VariableDefinition { .. } => {
-                todo!("Should variable definition be allowed as root (constant?)")
+            Node::VariableDefinition { var_type, name, data_type, value, public, attributes } => {
+                let mut file = CFile::new("".to_string());
+
+                let Some(data_type) = data_type else {
+                    panic!("Missing data_type");
+                };
+                let name = match var_type.data {
+                    VarType::Constant => format!("const {}", name.data),
+                    VarType::Variable => name.data
+                };
+                let (qualifiers, symbol) = self.translate_attributes(&attributes);
+                let declaration = format!("{}{}{}", qualifiers, self.translate_declaration(data_type, &name), symbol);
+
+                // in the .h => extern type name;
+                // in the .c => type name = value; (before the functions)
+                if public {
+                    file.header.push_str(&format!("extern {};\n", declaration));
+                } else {
+                    file.declarations.push_str("static ");
+                }
+                file.declarations.push_str(&declaration);
+                if let Some(value) = value {
+                    file.declarations.push_str(" = ");
+                    file.declarations.push_str(&self.translate_initializer(*value));
+                }
+                file.declarations.push_str(";\n");
+
+                file
             },
-            Node::FunctionDefinition { name, return_type, params, body, constructor } => {
+            Node::FunctionDefinition { name, return_type, params, body, constructor, public, attributes, .. } => {
                 let mut file = CFile::new("".to_string());
 
                 if constructor {
@@ -205,7 +797,10 @@ impl Translator {
                     return file; // Nothing to do
                 }
 
-                let mut fun_header = String::new();
+                let exported = public || name.data == "main";
+                let storage = if exported { "" } else { "static " };
+                let (qualifiers, symbol) = self.translate_attributes(&attributes);
+                let mut fun_header = qualifiers;
                 fun_header.push_str(&self.translate_type(return_type));
                 fun_header.push(' ');
                 fun_header.push_str(&name.data);
@@ -215,41 +810,48 @@ impl Translator {
                     if index != 0 {
                         fun_header.push_str(", ");
                     }
-                    fun_header.push_str(&self.translate_type(Some(param.data_type)));
-                    fun_header.push(' ');
-                    fun_header.push_str(&param.name.data);
+                    fun_header.push_str(&self.translate_declaration(param.data_type, &param.name.data));
                     index += 1;
                 }
                 fun_header.push(')');
 
             
-                if name.data != "main" {
+                if !exported {
+                    // in the .c => static type name(params, ...);
+                    file.declarations.push_str(&format!("{}{}{};\n", storage, fun_header, symbol));
+                } else if name.data != "main" {
                     // in the .h => type name(params, ...);
-                    file.header.push_str(&fun_header);
-                    file.header.push_str(";\n");
+                    file.header.push_str(&format!("{}{};\n", fun_header, symbol));
                 }
 
 
                 // in the .c => type name (params, ...) { body }
+                file.src.push_str(storage);
+                file.src.push_str(Self::translate_inline(&attributes));
                 file.src.push_str(&fun_header);
                 file.src.push_str(" { ");
-                index = 0;
-                for node in body.unwrap() {
-                    if index == 0 {
-                        file.src.push_str("\n");
-                    }
-                    for line in self.translate_node(node).lines() {
-                        file.src.push('\t');
-                        file.src.push_str(line);
-                        file.src.push(';');
-                        file.src.push_str("\n");
-                    }
-                    index += 1;
+                let body = body.unwrap();
+                if !body.is_empty() {
+                    file.src.push('\n');
                 }
+                file.src.push_str(&self.translate_statements(body));
                 file.src.push_str("}\n");
 
+                // Lifted lambdas go before the function using them
+                file.src = format!("{}{}", std::mem::take(&mut self.lifted).join(""), file.src);
+
                 file
             },
+            Node::Import { .. } => CFile::new("".to_string()), // Included by the module header
+            Node::TypeAlias { name, .. } => {
+                // Defined in the module header for the importing modules
+                self.translate_alias(&name.data);
+                CFile::new("".to_string())
+            },
+            // Defined by the included C headers
+            Node::ExternClass { .. } |
+            Node::ExternStruct { .. } |
+            Node::ExternConstant { .. } => CFile::new("".to_string()),
             Node::Include(path) => {
                 let mut file = CFile::new("".to_string());
 
@@ -266,8 +868,9 @@ impl Translator {
 
                 file
             },
-            Node::ClassDefinition { name, body } => {
+            Node::ClassDefinition { name, body, attributes, .. } => {
                 let mut file = CFile::new(name.data.clone());
+                self.class = Some(name.data.clone());
 
                 let mut struct_str = String::new();
 
@@ -275,10 +878,31 @@ impl Translator {
                 struct_str.push_str(&name.data);
                 struct_str.push_str("T {");
 
+                // malloc / free
+                self.require("#include <stdlib.h>\n".to_string());
+
+                let mut destructor = String::new();
                 let mut field_index = 0;
                 for node in body {
+                    // Static members are not part of the instance
+                    let (node, is_static) = match node.data {
+                        Node::Static(node) => (*node, true),
+                        _ => (node, false)
+                    };
                     match node.data {
-                        Node::VariableDefinition { var_type, name, data_type, value } => {
+                        Node::VariableDefinition { var_type, name: field_name, data_type, value, public, attributes } if is_static => {
+                            let global = self.translate_root(field_name.convert(Node::VariableDefinition { 
+                                var_type, 
+                                name: field_name.convert(format!("{}_{}", name.data, field_name.data)), 
+                                data_type, 
+                                value, 
+                                public,
+                                attributes
+                            }));
+                            file.header.push_str(&global.header);
+                            file.declarations.push_str(&global.declarations);
+                        },
+                        Node::VariableDefinition { var_type, name, data_type, value, .. } => {
                             if field_index == 0 {
                                 struct_str.push('\n');
                             }
@@ -287,27 +911,31 @@ impl Translator {
                             _ = var_type; // Ignored
 
                             if let Some(data_type) = data_type {
-                                struct_str.push_str(&self.translate_type(Some(data_type)));
+                                struct_str.push_str(&self.translate_declaration(data_type, &name.data));
                             } else {
                                 panic!("Missing data_type");
                             }
 
-                            struct_str.push(' ');
-                            struct_str.push_str(&name.data);
-
-                            if let Some(_) = value {
-                                todo!("Class Field Default values");
-                            }
+                            _ = value; // Assigned by the constructors
 
                             struct_str.push_str(";\n");
                             field_index += 1;
                         },
-                        Node::FunctionDefinition { name: function_name, return_type, params, body, constructor } => {
+                        Node::FunctionDefinition { name: function_name, return_type, params, body, constructor, public, attributes, .. } => {
                             if body.is_none() {
                                 panic!("Class function shouldn't be external!");
                             }
 
-                            let mut fun_header = String::new();
+                            if function_name.data == "drop" {
+                                // The destructor is the start of the free function
+                                destructor = self.translate_statements(body.unwrap());
+                                file.src.push_str(&std::mem::take(&mut self.lifted).join(""));
+                                continue;
+                            }
+
+                            let storage = if public { "" } else { "static " };
+                            let (qualifiers, symbol) = self.translate_attributes(&attributes);
+                            let mut fun_header = qualifiers;
                             if constructor {
                                 fun_header.push_str(&name.data);
                                 fun_header.push('*');
@@ -315,34 +943,41 @@ impl Translator {
                                 fun_header.push_str(&self.translate_type(return_type));
                             }
                             fun_header.push(' ');
+                            fun_header.push_str(&name.data);
+                            fun_header.push('_');
                             fun_header.push_str(&function_name.data);
                             fun_header.push('(');
                             let mut index = 0;
-                            if !constructor {
+                            if !constructor && !is_static {
                                 // Push first default param (self)
                                 fun_header.push_str(&name.data);
                                 fun_header.push_str("* self");
+                                index += 1;
                             }
                             // Normal params
                             for param in params {
                                 if index != 0 {
                                     fun_header.push_str(", ");
                                 }
-                                fun_header.push_str(&self.translate_type(Some(param.data_type)));
-                                fun_header.push(' ');
-                                fun_header.push_str(&param.name.data);
+                                fun_header.push_str(&self.translate_declaration(param.data_type, &param.name.data));
                                 index += 1;
                             }
                             fun_header.push(')');
                         
-                            // in the .h => type name(params, ...);
-                            file.header.push_str(&fun_header);
-                            file.header.push_str(";\n");
+                            if public {
+                                // in the .h => type name(params, ...);
+                                file.header.push_str(&format!("{}{};\n", fun_header, symbol));
+                            } else {
+                                // in the .c => static type name(params, ...);
+                                file.declarations.push_str(&format!("{}{}{};\n", storage, fun_header, symbol));
+                            }
 
                             // in the .c => type name (params, ...) { body }
+                            let start = file.src.len();
+                            file.src.push_str(storage);
+                            file.src.push_str(Self::translate_inline(&attributes));
                             file.src.push_str(&fun_header);
                             file.src.push_str(" { ");
-                            let mut index = 0;
                             if constructor {
                                 // Allocate memory TODO: optimize in checker (later!)
                                 file.src.push_str("\n\t");
@@ -351,29 +986,37 @@ impl Translator {
                                 file.src.push_str(&name.data);
                                 file.src.push_str("));");
                             }
-                            for node in body.unwrap() {
-                                if index == 0 {
-                                    file.src.push_str("\n");
-                                }
-                                for line in self.translate_node(node).lines() {
-                                    file.src.push('\t');
-                                    file.src.push_str(line);
-                                    file.src.push(';');
-                                    file.src.push_str("\n");
-                                }
-                                index += 1;
+                            let body = body.unwrap();
+                            if !body.is_empty() || constructor {
+                                file.src.push('\n');
+                            }
+                            file.src.push_str(&self.translate_statements(body));
+                            if constructor {
+                                file.src.push_str("\treturn self;\n");
                             }
                             file.src.push_str("}\n");
+
+                            // Lifted lambdas go before the method using them
+                            file.src.insert_str(start, &std::mem::take(&mut self.lifted).join(""));
                         },
                         _ => {}
                     }
                 }
 
                 struct_str.push_str("} ");
+                struct_str.push_str(&self.translate_attributes(&attributes).0);
                 struct_str.push_str(&name.data);
                 struct_str.push_str(";");
 
+                // Destructor and free (called by the checker when an instance goes out of scope or is reassigned)
+                let class = &name.data;
+                file.header.push_str(&format!("void {class}_free({class}* self);\n"));
+                file.header.push_str(&format!("{class}* {class}_replace({class}* old, {class}* value);\n"));
+                file.src.push_str(&format!("void {class}_free({class}* self) {{ \n\tif (self == NULL) return;\n{destructor}\tfree(self);\n}}\n"));
+                file.src.push_str(&format!("{class}* {class}_replace({class}* old, {class}* value) {{ \n\tif (old != value) {class}_free(old);\n\treturn value;\n}}\n"));
+
                 file.header = format!("{}\n{}", struct_str, file.header);
+                self.class = None;
 
                 file
             }
@@ -384,8 +1027,37 @@ impl Translator {
     pub fn translate(&mut self) -> CProject {
         let mut project = CProject::new();
 
+        // The module header includes the imported modules and the classes of the module
+        let mut module = CFile::new(self.src.name.clone());
+        for node in self.ast.iter() {
+            if let Node::Import { module: name, .. } = &node.data {
+                self.imports.push(format!("#include \"{}.h\"\n", name.data));
+            }
+        }
+        module.prelude = self.imports.clone();
+        let includes: String = self.ast.clone().into_iter().filter(|node| matches!(node.data, Node::Include(_))).map(|node| self.translate_root(node).src).collect();
+        for node in self.ast.iter() {
+            if let Node::TypeAlias { name, data_type, .. } = &node.data {
+                self.aliases.push((name.data.clone(), data_type.clone()));
+            }
+            if let Node::ClassDefinition { name, .. } = &node.data {
+                if !self.classes.contains(&name.data) {
+                    self.classes.push(name.data.clone());
+                }
+                module.prelude.push(format!("#include \"{}.h\"\n", name.data));
+            }
+        }
+        project.merge(module, self.src.name.clone());
+
         while let Some(current) = self.current() {
-            let file = self.translate_root(current);
+            let class = matches!(current.data, Node::ClassDefinition { .. });
+            let mut file = self.translate_root(current);
+            file.prelude = std::mem::take(&mut self.prelude);
+            if class {
+                // Classes have their own files (and use the public symbols and C headers of the module)
+                file.prelude.splice(0..0, self.imports.clone());
+                file.src = format!("#include \"{}.h\"\n{}{}", self.src.name, includes, file.src);
+            }
 
             project.merge(file, self.src.name.clone());
 
@@ -397,4 +1069,19 @@ impl Translator {
         project
     }
 
-}
\ No newline at end of file
+}
+
+const BOUNDS_CHECK: &str = "#ifndef APLA_BOUNDS_CHECK
+#define APLA_BOUNDS_CHECK
+#include <stddef.h>
+#include <stdio.h>
+#include <stdlib.h>
+static inline size_t apla_bounds_check(size_t index, size_t length, const char* location) {
+	if (index >= length) {
+		fprintf(stderr, \"%s: index %zu out of bounds for length %zu\\n\", location, index, length);
+		abort();
+	}
+	return index;
+}
+#endif
+";